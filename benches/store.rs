@@ -0,0 +1,172 @@
+use std::{env, fs};
+
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use nbd::db::{Connection, ContactRepo};
+use nbd::models::{Contact, NewContact};
+use nbd::seed;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use tokio::runtime::Runtime;
+
+/// sqlx's default number of prepared statements kept per connection
+const STATEMENT_CACHE: usize = 100;
+
+const BATCH: usize = 500;
+const LIST_ROWS: usize = 50_000;
+
+/// A fresh database file, so commits cost what they do for real
+async fn database(name: &str, statement_cache: usize) -> Connection {
+    let path = env::temp_dir().join(format!("nbd-bench-{name}.db"));
+    let _ = fs::remove_file(&path);
+
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        .statement_cache_capacity(statement_cache);
+
+    let pool = SqlitePoolOptions::new()
+        .connect_with(options)
+        .await
+        .unwrap();
+
+    sqlx::migrate!().run(&pool).await.unwrap();
+
+    Connection::new(pool)
+}
+
+fn contacts(count: usize) -> Vec<Contact> {
+    seed::generate(count, 1, Utc::now())
+        .into_iter()
+        .map(|fake| fake.contact)
+        .collect()
+}
+
+/// Contacts with the metadata and history an import or seed would save
+fn new_contacts(count: usize) -> Vec<NewContact> {
+    seed::generate(count, 1, Utc::now())
+        .into_iter()
+        .map(|fake| NewContact {
+            metadata: fake.metadata,
+            interactions: fake.interactions,
+            ..NewContact::from(fake.contact)
+        })
+        .collect()
+}
+
+fn create(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let repo = &runtime.block_on(database("create", STATEMENT_CACHE));
+
+    let mut group = c.benchmark_group("create");
+
+    group.bench_function("one at a time", |b| {
+        b.to_async(&runtime).iter_batched(
+            || contacts(BATCH),
+            |contacts| async move {
+                for contact in contacts {
+                    repo.create_contact(contact).await.unwrap();
+                }
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("batched", |b| {
+        b.to_async(&runtime).iter_batched(
+            || contacts(BATCH),
+            |contacts| async move {
+                repo.create_contacts(contacts).await.unwrap();
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+fn list(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let repo = &runtime.block_on(async {
+        let repo = database("list", STATEMENT_CACHE).await;
+
+        let fakes = seed::generate(LIST_ROWS, 1, Utc::now())
+            .into_iter()
+            .map(|fake| (fake.contact, fake.metadata))
+            .collect();
+        repo.create_contacts_with_metadata(fakes).await.unwrap();
+
+        repo
+    });
+
+    let mut group = c.benchmark_group("list");
+    group.sample_size(10);
+
+    group.bench_function("50k with metadata", |b| {
+        b.to_async(&runtime).iter(|| async move {
+            repo.get_all_with_metadata().await.unwrap();
+        });
+    });
+
+    group.finish();
+}
+
+fn lookup(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("lookup");
+
+    for (name, statement_cache) in [("uncached", 0), ("cached", STATEMENT_CACHE)] {
+        let repo = &runtime.block_on(database(name, statement_cache));
+        let ids = &runtime
+            .block_on(repo.create_contacts(contacts(BATCH)))
+            .unwrap();
+
+        group.bench_function(name, |b| {
+            b.to_async(&runtime).iter(|| async move {
+                for id in ids {
+                    repo.get_contact_by_id(*id).await.unwrap();
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn import(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let repo = &runtime.block_on(database("import", STATEMENT_CACHE));
+
+    let mut group = c.benchmark_group("import");
+
+    group.bench_function("one row at a time", |b| {
+        b.to_async(&runtime).iter_batched(
+            || new_contacts(BATCH),
+            |contacts| async move {
+                for contact in contacts {
+                    repo.create_contacts_from_source("bench:one", vec![contact])
+                        .await
+                        .unwrap();
+                }
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("batched", |b| {
+        b.to_async(&runtime).iter_batched(
+            || new_contacts(BATCH),
+            |contacts| async move {
+                repo.create_contacts_from_source("bench:batched", contacts)
+                    .await
+                    .unwrap();
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, create, list, lookup, import);
+criterion_main!(benches);
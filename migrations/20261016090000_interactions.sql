@@ -0,0 +1,10 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS interactions
+(
+id INTEGER PRIMARY KEY AUTOINCREMENT,
+contact_id INTEGER NOT NULL,
+kind TEXT NOT NULL,
+note TEXT,
+occurred_at TEXT NOT NULL,
+FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
\ No newline at end of file
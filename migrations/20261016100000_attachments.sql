@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS attachments
+(
+id INTEGER PRIMARY KEY AUTOINCREMENT,
+contact_id INTEGER NOT NULL,
+filename TEXT NOT NULL,
+mime TEXT NOT NULL,
+size INTEGER NOT NULL,
+hash TEXT NOT NULL,
+created_at TEXT NOT NULL,
+FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
\ No newline at end of file
@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS reviews
+(
+id INTEGER PRIMARY KEY AUTOINCREMENT,
+started_at TEXT NOT NULL,
+completed_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS review_items
+(
+review_id INTEGER NOT NULL,
+contact_id INTEGER NOT NULL,
+reviewed_at TEXT NOT NULL,
+PRIMARY KEY (review_id, contact_id),
+FOREIGN KEY (review_id) REFERENCES reviews(id) ON DELETE CASCADE,
+FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS messenger_handles
+(
+contact_id INTEGER NOT NULL,
+platform TEXT NOT NULL,
+handle TEXT NOT NULL,
+PRIMARY KEY (contact_id, platform),
+FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
\ No newline at end of file
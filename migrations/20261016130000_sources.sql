@@ -0,0 +1,16 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS sources
+(
+contact_id INTEGER PRIMARY KEY,
+source TEXT NOT NULL,
+external_id TEXT,
+created_at TEXT NOT NULL,
+FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS sources_source ON sources (source);
+
+-- Every contact so far was entered by hand
+INSERT INTO sources (contact_id, source, created_at)
+SELECT contact_id, 'manual', created_at FROM contacts_metadata
+WHERE contact_id IN (SELECT id FROM contacts);
\ No newline at end of file
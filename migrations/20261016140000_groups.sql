@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS contact_groups
+(
+id INTEGER PRIMARY KEY AUTOINCREMENT,
+name TEXT NOT NULL UNIQUE,
+frequency TEXT,
+created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS contact_group_members
+(
+group_id INTEGER NOT NULL,
+contact_id INTEGER NOT NULL,
+PRIMARY KEY (group_id, contact_id),
+FOREIGN KEY (group_id) REFERENCES contact_groups(id) ON DELETE CASCADE,
+FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+);
\ No newline at end of file
@@ -0,0 +1,37 @@
+-- no-transaction
+-- Add migration script here
+-- Email and phone number become optional. SQLite cannot drop NOT NULL in
+-- place, so the table is rebuilt, with foreign keys off so that dropping the
+-- old table does not cascade to the rows that reference it.
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+CREATE TABLE contacts_new
+(
+id INTEGER PRIMARY KEY AUTOINCREMENT,
+first_name TEXT NOT NULL,
+last_name TEXT NOT NULL,
+display_name TEXT NOT NULL,
+email TEXT,
+phone_number TEXT
+);
+
+INSERT INTO contacts_new (id, first_name, last_name, display_name, email, phone_number)
+SELECT id, first_name, last_name, display_name, NULLIF(email, ''), NULLIF(phone_number, '')
+FROM contacts;
+
+-- Keep ids of deleted contacts from being reused. contacts_new only has a
+-- sequence row if it got rows, so replace it with a copy of the old one
+DELETE FROM sqlite_sequence WHERE name = 'contacts_new';
+
+INSERT INTO sqlite_sequence (name, seq)
+SELECT 'contacts_new', seq FROM sqlite_sequence WHERE name = 'contacts';
+
+DROP TABLE contacts;
+
+ALTER TABLE contacts_new RENAME TO contacts;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
\ No newline at end of file
@@ -1,4 +1,6 @@
 use clap::{Args, Parser, Subcommand};
+use nbd::models::{Cadence, ContactField};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -16,13 +18,52 @@ pub enum Commands {
     Edit(EditCommand),
 
     /// Get all contacts
-    Show,
+    Show(ShowCommand),
 
     /// Get a contact
     Get(GetCommand),
 
     /// Delete a contact
     Delete(DeleteCommand),
+
+    /// Pick a random contact to reach out to
+    Roulette(RouletteCommand),
+
+    /// Walk through contacts due this week
+    Review(ReviewCommand),
+
+    /// List overdue contacts, most overdue first
+    Due(DueCommand),
+
+    /// Attach a file to a contact
+    Attach(AttachCommand),
+
+    /// List or open a contact's attachments
+    Attachments(AttachmentsCommand),
+
+    /// Check the database for corruption and inconsistencies
+    Doctor(DoctorCommand),
+
+    /// Set or remove a contact's messenger handle
+    Handle(HandleCommand),
+
+    /// Open a chat with a contact in their messenger
+    Message(MessageCommand),
+
+    /// Delete every contact that came from a source, e.g. a bad import
+    Rollback(RollbackCommand),
+
+    /// Import contacts from a file with an importer plugin
+    Import(ImportCommand),
+
+    /// Manage groups of contacts that share a cadence
+    Group(GroupCommand),
+
+    /// Fill the database with fake contacts for testing and demos
+    Seed(SeedCommand),
+
+    /// Summarize how many contacts are due, e.g. for a shell prompt
+    Status(StatusCommand),
 }
 
 #[derive(Args)]
@@ -41,6 +82,14 @@ pub struct CreateCommand {
 
     #[arg(short, long, value_name = "Phone")]
     pub phone_number: Option<String>,
+
+    /// Parse name, email and phone from a single pasted line
+    #[arg(
+        long,
+        value_name = "TEXT",
+        conflicts_with_all = ["first_name", "last_name", "display_name", "email", "phone_number"]
+    )]
+    pub freeform: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -62,12 +111,39 @@ pub struct EditCommand {
 
     #[arg(short, long, value_name = "Phone")]
     pub phone_number: Option<String>,
+
+    /// Clear a field, e.g. --clear phone
+    #[arg(long, value_name = "FIELD")]
+    pub clear: Vec<ContactField>,
+}
+
+#[derive(Args, Debug)]
+pub struct ShowCommand {
+    /// Only show contacts from this source, e.g. manual or csv:friends.csv
+    #[arg(long)]
+    pub source: Option<String>,
+
+    /// Render each contact with this template instead of the default output
+    #[arg(long, value_name = "FILE")]
+    pub template: Option<PathBuf>,
+
+    /// Show each contact's last interaction and how many days overdue they are
+    #[arg(long, conflicts_with = "template")]
+    pub with_activity: bool,
 }
 
 #[derive(Args, Debug)]
 pub struct GetCommand {
     /// ID of contact to get
     pub id: i64,
+
+    /// Print timestamps as RFC 3339 instead of relative to now
+    #[arg(long)]
+    pub absolute_dates: bool,
+
+    /// Render the contact with this template instead of the default output
+    #[arg(long, value_name = "FILE", conflicts_with = "absolute_dates")]
+    pub template: Option<PathBuf>,
 }
 
 #[derive(Args, Debug)]
@@ -75,3 +151,171 @@ pub struct DeleteCommand {
     /// ID of contact to delete
     pub id: i64,
 }
+
+#[derive(Args, Debug)]
+pub struct RouletteCommand {
+    /// Print timestamps as RFC 3339 instead of relative to now
+    #[arg(long)]
+    pub absolute_dates: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ReviewCommand {
+    /// Print timestamps as RFC 3339 instead of relative to now
+    #[arg(long)]
+    pub absolute_dates: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DueCommand {
+    /// Only list contacts at this overdue level or worse, e.g. lost-touch
+    #[arg(long)]
+    pub level: Option<String>,
+
+    /// Print timestamps as RFC 3339 instead of relative to now
+    #[arg(long)]
+    pub absolute_dates: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct AttachCommand {
+    /// ID of contact to attach the file to
+    pub id: i64,
+
+    /// File to attach
+    pub path: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct AttachmentsCommand {
+    /// ID of contact whose attachments to list
+    pub id: i64,
+
+    /// Open the attachment with this ID
+    #[arg(short, long, value_name = "ATTACHMENT ID")]
+    pub open: Option<i64>,
+}
+
+#[derive(Args, Debug)]
+pub struct DoctorCommand {
+    /// Repair the problems that can be fixed without losing data
+    #[arg(long)]
+    pub fix: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct HandleCommand {
+    /// ID of contact the handle belongs to
+    pub id: i64,
+
+    /// Messenger, e.g. signal, whatsapp or telegram
+    pub platform: String,
+
+    /// Username or phone number on that messenger
+    #[arg(required_unless_present = "remove")]
+    pub handle: Option<String>,
+
+    /// Remove the contact's handle on this messenger
+    #[arg(long, conflicts_with = "handle")]
+    pub remove: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct MessageCommand {
+    /// ID of contact to message
+    pub id: i64,
+
+    /// Messenger to use, defaults to the contact's first handle
+    #[arg(long, value_name = "PLATFORM")]
+    pub via: Option<String>,
+
+    /// Print the link instead of opening it
+    #[arg(long)]
+    pub print: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct RollbackCommand {
+    /// Source whose contacts to delete, e.g. csv:friends.csv
+    pub source: String,
+
+    /// Skip the confirmation prompt
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ImportCommand {
+    /// File to import
+    #[arg(required_unless_present = "list")]
+    pub path: Option<PathBuf>,
+
+    /// Importer to read the file with, e.g. vcf for nbd-import-vcf
+    #[arg(long, value_name = "IMPORTER", required_unless_present = "list")]
+    pub with: Option<String>,
+
+    /// List the importers that are installed
+    #[arg(long, conflicts_with_all = ["path", "with"])]
+    pub list: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SeedCommand {
+    /// How many contacts to add
+    #[arg(long, default_value_t = 100)]
+    pub count: usize,
+
+    /// Also generate cadences, notes, and interaction history, instead of
+    /// contacts that have never been seen
+    #[arg(long)]
+    pub fake: bool,
+
+    /// Seed for the generator, to get the same contacts again
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+pub struct StatusCommand {
+    /// Print stable key=value counts on one line, for scripts
+    #[arg(long)]
+    pub porcelain: bool,
+
+    /// Only count contacts from this source, e.g. manual or csv:friends.csv
+    #[arg(long)]
+    pub source: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct GroupCommand {
+    #[command(subcommand)]
+    pub command: GroupCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GroupCommands {
+    /// Create a group
+    Create(GroupCreateCommand),
+
+    /// Add a contact to a group
+    Add(GroupAddCommand),
+}
+
+#[derive(Args, Debug)]
+pub struct GroupCreateCommand {
+    /// Name of the group, e.g. "close friends"
+    pub name: String,
+
+    /// Cadence for members without one of their own
+    #[arg(long)]
+    pub cadence: Option<Cadence>,
+}
+
+#[derive(Args, Debug)]
+pub struct GroupAddCommand {
+    /// Name of the group
+    pub group: String,
+
+    /// ID of contact to add
+    pub id: i64,
+}
@@ -1,14 +1,38 @@
 use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process;
 
 mod commander;
-use nbd::{db, models};
+use nbd::{db, importer, models, seed, utils};
 
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use clap::Parser;
-use commander::{Cli, Commands};
-use db::{Connection, ContactRepo};
-use models::{Contact, ContactBuilder};
+use commander::{Cli, Commands, GroupCommands};
+use db::{
+    AttachmentRepo, BlobStore, Connection, ContactRepo, GroupRepo, HealthRepo, InteractionRepo,
+    MessengerRepo, MetadataRepo, ReviewRepo, SourceRepo, WriteLock, CONTACT_TABLES,
+};
+use importer::Importer;
+use models::{
+    Attachment, Cadence, Contact, ContactBuilder, ContactWithMetadata, DeepLinks, Freeform, Group,
+    Interaction, MessengerHandle, Metadata, NewContact, OverdueTiers, RequiredFields, Source,
+};
+use rand::distributions::{Distribution, WeightedIndex};
 use sqlx::SqlitePool;
+use utils::{AppError, Messages};
 
+const SNOOZE_DAYS: i64 = 7;
+const REVIEW_DAYS: i64 = 7;
+
+/// ANSI colors for overdue tiers, mildest first. Tiers beyond the last color
+/// reuse it.
+const TIER_COLORS: [&str; 3] = ["33", "31", "1;31"];
+
+// One match arm per subcommand, so this grows with the CLI
+#[allow(clippy::too_many_lines)]
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
@@ -19,38 +43,142 @@ async fn main() -> anyhow::Result<()> {
 
     let cli = Cli::parse();
 
+    let messages = Messages::from_env();
+
     // You can check for the existence of subcommands, and if found use their
     // matches just as you would the top level cmd
     match &cli.command {
         Commands::Create(value) => {
-            let contact = Contact::new(
-                value.first_name.as_deref().unwrap_or(""),
-                value.last_name.as_deref().unwrap_or(""),
-                value.email.as_deref().unwrap_or(""),
-                value.phone_number.as_deref().unwrap_or(""),
-            );
+            let contact = if let Some(line) = &value.freeform {
+                let freeform = Freeform::parse(line);
+
+                print_freeform(&freeform, messages);
+
+                let answer = prompt(&messages.get("prompt-save-contact", &[]))?;
+
+                if !answer.is_empty() && !is_yes(&answer) {
+                    println!("{}", messages.get("contact-discarded", &[]));
+
+                    return Ok(());
+                }
+
+                freeform.into_contact()
+            } else {
+                Contact::new(
+                    value.first_name.as_deref().unwrap_or(""),
+                    value.last_name.as_deref().unwrap_or(""),
+                    value.email.as_deref().unwrap_or(""),
+                    value.phone_number.as_deref().unwrap_or(""),
+                )
+            }
+            .and_then(|contact| RequiredFields::from_env().check(&contact).map(|()| contact))
+            .map_err(|error| anyhow::anyhow!(messages.error(&error)))?;
 
-            let contact = contact.unwrap();
+            let _lock = write_lock(messages)?;
 
-            let id = data_repo.create_contact(contact).await?;
+            let ids = data_repo
+                .create_contacts_from_source(Source::MANUAL, vec![NewContact::from(contact)])
+                .await?;
+            let id = ids[0];
 
-            println!("Successfully saved contact {id}");
+            println!("{}", messages.get("contact-saved", &[("id", &id)]));
         }
         Commands::Edit(value) => {
-            let contact = ContactBuilder::new(
+            let mut contact = ContactBuilder::new(
                 value.id,
                 value.first_name.clone(),
                 value.last_name.clone(),
-                value.display_name.clone(),
                 value.email.clone(),
                 value.phone_number.clone(),
+                value.display_name.clone(),
+                &value.clear,
             )
-            .unwrap();
+            .and_then(|contact| {
+                RequiredFields::from_env()
+                    .check_edit(&contact)
+                    .map(|()| contact)
+            })
+            .map_err(|error| anyhow::anyhow!(messages.error(&error)))?;
+
+            // Held from reading the current name, so another edit can't make
+            // the recomputed one stale
+            let _lock = write_lock(messages)?;
+
+            if contact.changes_name() {
+                let current = data_repo.get_contact_by_id(value.id).await?;
+                let name = contact.recomputed_display_name(&current.contact);
 
-            let _ = data_repo.update_contact(contact).await;
+                if name != current.contact.display_name {
+                    let answer = prompt(&messages.get("prompt-display-name", &[("name", &name)]))?;
+
+                    if is_yes(&answer) {
+                        contact.update.display_name = models::FieldUpdate::Set(name);
+                    }
+                }
+            }
+
+            data_repo.update_contact(contact).await?;
+
+            println!("{}", messages.get("contact-updated", &[]));
         }
-        Commands::Show => {
-            let contacts = data_repo.get_all_contacts().await?;
+        Commands::Show(value) => {
+            let contact_ids = match &value.source {
+                Some(source) => Some(data_repo.get_contact_ids_by_source(source).await?),
+                None => None,
+            };
+            let is_shown = |id: i64| contact_ids.as_ref().is_none_or(|ids| ids.contains(&id));
+
+            if let Some(path) = &value.template {
+                let template = fs::read_to_string(path)?;
+
+                for row in data_repo.get_all_with_metadata().await? {
+                    if is_shown(row.id) {
+                        println!(
+                            "{}",
+                            render_template(&template, row.id, &row.contact, &row.metadata)?
+                        );
+                    }
+                }
+
+                return Ok(());
+            }
+
+            if value.with_activity {
+                let now = Utc::now();
+                let tiers = OverdueTiers::from_env();
+                let activity = data_repo.get_activity().await?;
+
+                println!("{}", messages.get("activity-header", &[]));
+
+                for row in data_repo.get_all_with_metadata().await? {
+                    if !is_shown(row.id) {
+                        continue;
+                    }
+
+                    let last = activity
+                        .binary_search_by_key(&row.id, |activity| activity.contact_id)
+                        .ok()
+                        .map(|index| &activity[index]);
+
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}",
+                        row.id,
+                        row.contact.display_name,
+                        last.map_or_else(
+                            || "-".to_owned(),
+                            |last| utils::humanize(last.last_occurred_at, now, &messages)
+                        ),
+                        last.map_or("-", |last| last.last_kind.as_str()),
+                        days_overdue(&row.metadata, now, &tiers)
+                    );
+                }
+
+                return Ok(());
+            }
+
+            let mut contacts = data_repo.get_all_contacts().await?;
+
+            contacts.retain(|indexed| is_shown(indexed.id));
 
             println!("{contacts:?}");
         }
@@ -58,17 +186,1009 @@ async fn main() -> anyhow::Result<()> {
             let id = value.id;
 
             let contact = data_repo.get_contact_by_id(id).await?;
+            let metadata = data_repo.get_metadata_by_id(id).await?;
+
+            if let Some(path) = &value.template {
+                let template = fs::read_to_string(path)?;
+
+                println!(
+                    "{}",
+                    render_template(&template, id, &contact.contact, &metadata)?
+                );
+
+                return Ok(());
+            }
 
             println!("{contact:?}");
+
+            print_dates(&metadata, value.absolute_dates, messages);
+            print_handles(&data_repo, id).await?;
+
+            if let Some(source) = data_repo.get_source_by_contact_id(id).await? {
+                println!("{}: {source}", messages.get("label-source", &[]));
+            }
+
+            let groups = data_repo.get_groups_by_contact_id(id).await?;
+
+            if !groups.is_empty() {
+                let names: Vec<&str> = groups
+                    .iter()
+                    .map(|indexed| indexed.group.name.as_str())
+                    .collect();
+
+                println!(
+                    "{}: {}",
+                    messages.get("label-groups", &[]),
+                    names.join(", ")
+                );
+            }
         }
         Commands::Delete(value) => {
             let id = value.id;
 
+            let _lock = write_lock(messages)?;
+
             let contact_id = data_repo.delete_contact_by_id(id).await?;
 
-            println!("Successfully deleted contact {contact_id}");
+            println!(
+                "{}",
+                messages.get("contact-deleted", &[("id", &contact_id)])
+            );
+        }
+        Commands::Roulette(value) => {
+            let now = Utc::now();
+            let candidates: Vec<ContactWithMetadata> = data_repo
+                .get_all_with_metadata()
+                .await?
+                .into_iter()
+                .filter(|candidate| !candidate.metadata.is_archived)
+                .collect();
+
+            if candidates.is_empty() {
+                println!("{}", messages.get("roulette-empty", &[]));
+
+                return Ok(());
+            }
+
+            // Favor contacts who have gone the longest without being seen
+            let weights = candidates
+                .iter()
+                .map(|candidate| candidate.metadata.days_since_seen(now) + 1);
+            let index = WeightedIndex::new(weights)?.sample(&mut rand::thread_rng());
+            let candidate = &candidates[index];
+
+            print_contact_card(
+                &data_repo,
+                candidate.id,
+                &candidate.contact,
+                &candidate.metadata,
+                value.absolute_dates,
+                &messages,
+            )
+            .await?;
+
+            match prompt(&messages.get("roulette-prompt", &[]))?
+                .to_lowercase()
+                .as_str()
+            {
+                "l" => log_interaction(&data_repo, candidate.id, &messages).await?,
+                "s" => snooze(&data_repo, candidate.id, value.absolute_dates, &messages).await?,
+                _ => println!("{}", messages.get("contact-skipped", &[])),
+            }
+        }
+        Commands::Review(value) => {
+            let review_id = {
+                // Two reviews starting at once would each open one otherwise
+                let _lock = write_lock(messages)?;
+
+                match data_repo.get_open_review_id().await? {
+                    Some(review_id) => review_id,
+                    None => data_repo.create_review().await?,
+                }
+            };
+
+            let reviewed = data_repo.get_reviewed_contact_ids(review_id).await?;
+
+            let due: Vec<Metadata> = data_repo
+                .get_due_metadata(Utc::now() + Duration::days(REVIEW_DAYS))
+                .await?
+                .into_iter()
+                .filter(|metadata| !reviewed.contains(&metadata.contact_id))
+                .collect();
+
+            let total = due.len();
+
+            for (position, metadata) in due.iter().enumerate() {
+                let contact = data_repo.get_contact_by_id(metadata.contact_id).await?;
+
+                println!("\n({}/{total})", position + 1);
+
+                print_contact_card(
+                    &data_repo,
+                    contact.id,
+                    &contact.contact,
+                    metadata,
+                    value.absolute_dates,
+                    &messages,
+                )
+                .await?;
+
+                match prompt(&messages.get("review-prompt", &[]))?
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "l" => log_interaction(&data_repo, contact.id, &messages).await?,
+                    "s" => snooze(&data_repo, contact.id, value.absolute_dates, &messages).await?,
+                    "c" => {
+                        let answer = prompt(&messages.get("prompt-cadence", &[]))?;
+
+                        let cadence = if answer.eq_ignore_ascii_case("none") {
+                            Ok(None)
+                        } else {
+                            answer.parse::<Cadence>().map(Some)
+                        };
+
+                        match cadence {
+                            Ok(cadence) => {
+                                let _lock = write_lock(messages)?;
+
+                                data_repo.update_cadence(contact.id, cadence).await?;
+
+                                // Reschedule from when they were last seen under the new cadence
+                                let seen_at = metadata.last_seen_at.unwrap_or(metadata.created_at);
+                                let next_reminder_at =
+                                    cadence.map(|cadence| seen_at + cadence.duration());
+
+                                data_repo
+                                    .update_next_reminder(contact.id, next_reminder_at)
+                                    .await?;
+
+                                println!("{}", messages.get("cadence-updated", &[]));
+                            }
+                            // Skipped rather than retried, so the contact still
+                            // counts as reviewed and the review moves on
+                            Err(error) => {
+                                eprintln!("{}", messages.error(&error));
+                                println!("{}", messages.get("contact-skipped", &[]));
+                            }
+                        }
+                    }
+                    "a" => {
+                        let _lock = write_lock(messages)?;
+
+                        data_repo.update_archived(contact.id, true).await?;
+
+                        println!("{}", messages.get("contact-archived", &[]));
+                    }
+                    "q" => {
+                        println!("{}", messages.get("review-paused", &[]));
+
+                        return Ok(());
+                    }
+                    _ => println!("{}", messages.get("contact-skipped", &[])),
+                }
+
+                let _lock = write_lock(messages)?;
+
+                data_repo.mark_reviewed(review_id, contact.id).await?;
+            }
+
+            let _lock = write_lock(messages)?;
+
+            data_repo.complete_review(review_id).await?;
+
+            println!("{}", messages.get("review-complete", &[]));
+        }
+        Commands::Due(value) => {
+            let now = Utc::now();
+            let tiers = OverdueTiers::from_env();
+
+            let minimum = value
+                .level
+                .as_deref()
+                .map(|level| tiers.position(level))
+                .transpose()
+                .map_err(|error| anyhow::anyhow!(messages.error(&error)))?;
+
+            let mut overdue = Vec::new();
+
+            for row in data_repo.get_all_with_metadata().await? {
+                let Some(ratio) = row.metadata.overdue_ratio(now) else {
+                    continue;
+                };
+
+                let level = tiers.level(ratio);
+
+                if minimum
+                    .is_some_and(|minimum| level.is_none_or(|(position, _)| position < minimum))
+                {
+                    continue;
+                }
+
+                let label = level.map_or_else(String::new, |(position, tier)| {
+                    paint_tier(&format!("[{}] ", tier.name), position)
+                });
+
+                overdue.push((ratio, label, row));
+            }
+
+            if overdue.is_empty() {
+                println!("{}", messages.get("due-empty", &[]));
+            }
+
+            overdue.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+            for (_, label, row) in overdue {
+                let seen_at = row.metadata.last_seen_at.unwrap_or(row.metadata.created_at);
+                let seen = format_date(seen_at, value.absolute_dates, messages);
+
+                println!(
+                    "{label}{} {}",
+                    row.id,
+                    messages.get(
+                        "due-row",
+                        &[("name", &row.contact.display_name), ("seen", &seen)]
+                    )
+                );
+            }
+        }
+        Commands::Attach(value) => {
+            let bytes = fs::read(&value.path)?;
+            let filename = value.path.file_name().map_or_else(
+                || value.path.to_string_lossy(),
+                |name| name.to_string_lossy(),
+            );
+
+            let _lock = write_lock(messages)?;
+
+            // Fails on an unknown contact before a blob is stored for it
+            data_repo.get_contact_by_id(value.id).await?;
+
+            let hash = BlobStore::new(data_dir()?).put(&bytes)?;
+            let attachment =
+                Attachment::new(value.id, &filename, i64::try_from(bytes.len())?, &hash);
+
+            let id = data_repo.create_attachment(attachment).await?;
+
+            println!("{}", messages.get("attachment-saved", &[("id", &id)]));
+        }
+        Commands::Attachments(value) => {
+            let attachments = data_repo.get_attachments_by_contact_id(value.id).await?;
+
+            if let Some(attachment_id) = value.open {
+                let Some(indexed) = attachments
+                    .iter()
+                    .find(|indexed| indexed.id == attachment_id)
+                else {
+                    anyhow::bail!(messages.get(
+                        "attachment-not-found",
+                        &[("id", &attachment_id), ("contact", &value.id)]
+                    ));
+                };
+
+                open_with_default_app(BlobStore::new(data_dir()?).path(&indexed.attachment.hash))?;
+
+                return Ok(());
+            }
+
+            if attachments.is_empty() {
+                println!("{}", messages.get("attachments-empty", &[]));
+            }
+
+            for indexed in attachments {
+                let attachment = indexed.attachment;
+
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    indexed.id,
+                    attachment.filename,
+                    attachment.mime,
+                    messages.get("attachment-size", &[("size", &attachment.size)])
+                );
+            }
+        }
+        Commands::Doctor(value) => {
+            // Problems doctor can repair, and problems that need a person
+            let mut fixable = false;
+            let mut unfixable = false;
+
+            let integrity = data_repo.integrity_check().await?;
+
+            if integrity.is_empty() {
+                println!("{}", messages.get("doctor-integrity-ok", &[]));
+            }
+
+            for detail in &integrity {
+                println!(
+                    "{}",
+                    messages.get("doctor-integrity-failed", &[("detail", detail)])
+                );
+
+                unfixable = true;
+            }
+
+            let expected = sqlx::migrate!()
+                .iter()
+                .map(|migration| migration.version)
+                .max()
+                .unwrap_or_default();
+
+            match data_repo.get_schema_version().await? {
+                Some(version) if version == expected => {
+                    println!(
+                        "{}",
+                        messages.get("doctor-schema-ok", &[("version", &version)])
+                    );
+                }
+                Some(version) => {
+                    println!(
+                        "{}",
+                        messages.get(
+                            "doctor-schema-mismatch",
+                            &[("version", &version), ("expected", &expected)]
+                        )
+                    );
+
+                    unfixable = true;
+                }
+                None => {
+                    println!(
+                        "{}",
+                        messages.get("doctor-schema-unknown", &[("expected", &expected)])
+                    );
+
+                    unfixable = true;
+                }
+            }
+
+            for table in CONTACT_TABLES {
+                let count = data_repo.count_orphans(table).await?;
+
+                if count > 0 {
+                    println!(
+                        "{}",
+                        messages.get("doctor-orphans", &[("count", &count), ("table", &table)])
+                    );
+
+                    fixable = true;
+                }
+            }
+
+            let missing = data_repo.get_contact_ids_missing_metadata().await?;
+
+            if !missing.is_empty() {
+                println!(
+                    "{}",
+                    messages.get("doctor-missing-metadata", &[("count", &missing.len())])
+                );
+
+                fixable = true;
+            }
+
+            // Blobs left behind by deleted attachments and contacts
+            let blobs = BlobStore::new(data_dir()?);
+            let referenced = data_repo.get_attachment_hashes().await?;
+            let unreferenced: Vec<String> = blobs
+                .hashes()?
+                .into_iter()
+                .filter(|hash| referenced.binary_search(hash).is_err())
+                .collect();
+
+            if !unreferenced.is_empty() {
+                println!(
+                    "{}",
+                    messages.get(
+                        "doctor-unreferenced-blobs",
+                        &[("count", &unreferenced.len())]
+                    )
+                );
+
+                fixable = true;
+            }
+
+            let required = RequiredFields::from_env();
+
+            for indexed in data_repo.get_all_contacts().await? {
+                if let Err(error) = indexed
+                    .contact
+                    .validate()
+                    .and_then(|()| required.check(&indexed.contact))
+                {
+                    let problem = messages.error(&error);
+
+                    println!(
+                        "{}",
+                        messages.get(
+                            "doctor-invalid-contact",
+                            &[("id", &indexed.id), ("problem", &problem)]
+                        )
+                    );
+
+                    unfixable = true;
+                }
+            }
+
+            if fixable && value.fix {
+                let _lock = write_lock(messages)?;
+
+                let removed = data_repo.delete_orphans().await?;
+                let created = data_repo.create_missing_metadata().await?;
+
+                for hash in &unreferenced {
+                    blobs.remove(hash)?;
+                }
+
+                println!(
+                    "{}",
+                    messages.get(
+                        "doctor-fixed",
+                        &[
+                            ("removed", &removed),
+                            ("created", &created),
+                            ("blobs", &unreferenced.len())
+                        ]
+                    )
+                );
+            } else if fixable {
+                println!("{}", messages.get("doctor-fix-hint", &[]));
+            }
+
+            if !fixable && !unfixable {
+                println!("{}", messages.get("doctor-healthy", &[]));
+            }
+
+            if unfixable || (fixable && !value.fix) {
+                process::exit(1);
+            }
+        }
+        Commands::Handle(value) => {
+            let platform = value.platform.to_lowercase();
+
+            let _lock = write_lock(messages)?;
+
+            if value.remove {
+                if !data_repo.delete_handle(value.id, &platform).await? {
+                    anyhow::bail!(messages.get(
+                        "handle-not-found",
+                        &[("id", &value.id), ("platform", &platform)]
+                    ));
+                }
+
+                println!(
+                    "{}",
+                    messages.get("handle-removed", &[("platform", &platform)])
+                );
+
+                return Ok(());
+            }
+
+            let handle = MessengerHandle::new(
+                value.id,
+                &platform,
+                value.handle.as_deref().unwrap_or_default(),
+                &DeepLinks::from_env(),
+            )
+            .map_err(|error| anyhow::anyhow!(messages.error(&error)))?;
+
+            data_repo.set_handle(handle).await?;
+
+            println!(
+                "{}",
+                messages.get("handle-saved", &[("platform", &platform)])
+            );
+        }
+        Commands::Message(value) => {
+            let handles = data_repo.get_handles_by_contact_id(value.id).await?;
+
+            let handle = value.via.as_ref().map_or_else(
+                || handles.first(),
+                |platform| {
+                    handles
+                        .iter()
+                        .find(|handle| handle.platform.eq_ignore_ascii_case(platform))
+                },
+            );
+
+            let Some(link) = handle.and_then(|handle| DeepLinks::from_env().link(handle)) else {
+                match &value.via {
+                    Some(platform) => anyhow::bail!(messages.get(
+                        "handle-not-found",
+                        &[("id", &value.id), ("platform", platform)]
+                    )),
+                    None => anyhow::bail!(messages.get("handles-empty", &[("id", &value.id)])),
+                }
+            };
+
+            println!("{link}");
+
+            if !value.print {
+                open_with_default_app(&link)?;
+            }
+        }
+        Commands::Rollback(value) => {
+            let source = &value.source;
+            let count = data_repo.get_contact_ids_by_source(source).await?.len();
+
+            if count == 0 {
+                println!("{}", messages.get("rollback-empty", &[("source", source)]));
+
+                return Ok(());
+            }
+
+            if !value.yes {
+                let answer = prompt(
+                    &messages.get("prompt-rollback", &[("count", &count), ("source", source)]),
+                )?;
+
+                if !is_yes(&answer) {
+                    return Ok(());
+                }
+            }
+
+            let _lock = write_lock(messages)?;
+
+            let deleted = data_repo.delete_contacts_by_source(source).await?;
+
+            println!(
+                "{}",
+                messages.get(
+                    "rollback-complete",
+                    &[("count", &deleted), ("source", source)]
+                )
+            );
+        }
+        Commands::Import(value) => {
+            let dirs = Importer::search_path();
+
+            if value.list {
+                let importers = Importer::discover(&dirs);
+
+                if importers.is_empty() {
+                    println!("{}", messages.get("importers-empty", &[]));
+                }
+
+                for importer in importers {
+                    println!("{}\t{}", importer.name, importer.path.display());
+                }
+
+                return Ok(());
+            }
+
+            let (Some(path), Some(name)) = (&value.path, &value.with) else {
+                unreachable!("clap requires a path and an importer without --list");
+            };
+
+            let importer = Importer::find(name, &dirs).ok_or_else(|| {
+                anyhow::anyhow!(messages.error(&AppError::UnknownImporter(name.clone())))
+            })?;
+
+            let bytes = fs::read(path)?;
+            let filename = path.file_name().map_or_else(
+                || path.to_string_lossy(),
+                |file_name| file_name.to_string_lossy(),
+            );
+
+            let records = importer
+                .run(&filename, &bytes)
+                .map_err(|error| anyhow::anyhow!(messages.error(&error)))?;
+
+            let required = RequiredFields::from_env();
+
+            let mut contacts = Vec::with_capacity(records.len());
+
+            for (index, record) in records.into_iter().enumerate() {
+                let contact = record
+                    .to_contact()
+                    .and_then(|contact| required.check(&contact).map(|()| contact));
+
+                match contact {
+                    Ok(contact) => contacts.push(NewContact {
+                        external_id: record.external_id,
+                        ..NewContact::from(contact)
+                    }),
+                    Err(error) => {
+                        let error = messages.error(&error);
+
+                        eprintln!(
+                            "{}",
+                            messages.get(
+                                "import-skipped",
+                                &[("row", &(index + 1)), ("error", &error)]
+                            )
+                        );
+                    }
+                }
+            }
+
+            let source = format!("{name}:{filename}");
+
+            let _lock = write_lock(messages)?;
+
+            let contact_ids = data_repo
+                .create_contacts_from_source(&source, contacts)
+                .await?;
+
+            println!(
+                "{}",
+                messages.get(
+                    "import-complete",
+                    &[("count", &contact_ids.len()), ("source", &source)]
+                )
+            );
+        }
+        Commands::Group(value) => match &value.command {
+            GroupCommands::Create(value) => {
+                let group = Group::new(&value.name, value.cadence)
+                    .map_err(|error| anyhow::anyhow!(messages.error(&error)))?;
+                let name = group.name.clone();
+
+                let _lock = write_lock(messages)?;
+
+                data_repo.create_group(group).await.map_err(|error| {
+                    let localized = error
+                        .downcast_ref::<AppError>()
+                        .map(|error| messages.error(error));
+
+                    localized.map_or(error, |message| anyhow::anyhow!(message))
+                })?;
+
+                println!("{}", messages.get("group-created", &[("name", &name)]));
+            }
+            GroupCommands::Add(value) => {
+                let Some(indexed) = data_repo.get_group_by_name(&value.group).await? else {
+                    let error = AppError::UnknownGroup(value.group.clone());
+
+                    anyhow::bail!(messages.error(&error));
+                };
+
+                let _lock = write_lock(messages)?;
+
+                data_repo.add_member(indexed.id, value.id).await?;
+
+                // Start the reminder schedule for someone the group gave a cadence
+                let metadata = data_repo.get_metadata_by_id(value.id).await?;
+
+                if let (None, Some(cadence)) = (metadata.next_reminder_at, metadata.cadence()) {
+                    let seen_at = metadata.last_seen_at.unwrap_or(metadata.created_at);
+
+                    data_repo
+                        .update_next_reminder(value.id, Some(seen_at + cadence.duration()))
+                        .await?;
+                }
+
+                println!(
+                    "{}",
+                    messages.get(
+                        "group-member-added",
+                        &[("id", &value.id), ("name", &indexed.group.name)]
+                    )
+                );
+            }
+        },
+        Commands::Status(value) => {
+            let now = Utc::now();
+
+            let counts = data_repo
+                .get_status_counts(now, now + Duration::days(REVIEW_DAYS), value.source.clone())
+                .await?;
+
+            if value.porcelain {
+                println!("{}", counts.porcelain());
+            } else {
+                println!(
+                    "{}",
+                    messages.get(
+                        "status-summary",
+                        &[
+                            ("due", &counts.due),
+                            ("upcoming", &counts.upcoming),
+                            ("contacts", &counts.contacts)
+                        ]
+                    )
+                );
+            }
+        }
+        Commands::Seed(value) => {
+            // Print the seed even when it was picked for us, so the run can be repeated
+            let seed = value.seed.unwrap_or_else(rand::random);
+            let source = format!("seed:{seed}");
+
+            let contacts = seed::generate(value.count, seed, Utc::now())
+                .into_iter()
+                .map(|fake| {
+                    if value.fake {
+                        NewContact {
+                            metadata: fake.metadata,
+                            interactions: fake.interactions,
+                            ..NewContact::from(fake.contact)
+                        }
+                    } else {
+                        NewContact::from(fake.contact)
+                    }
+                })
+                .collect();
+
+            let _lock = write_lock(messages)?;
+
+            let contact_ids = data_repo
+                .create_contacts_from_source(&source, contacts)
+                .await?;
+
+            println!(
+                "{}",
+                messages.get(
+                    "seed-complete",
+                    &[
+                        ("count", &contact_ids.len()),
+                        ("seed", &seed),
+                        ("source", &source)
+                    ]
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn print_dates(metadata: &Metadata, absolute_dates: bool, messages: Messages) {
+    let dates = [
+        ("label-created", Some(metadata.created_at)),
+        ("label-updated", Some(metadata.updated_at)),
+        ("label-last-seen", metadata.last_seen_at),
+        ("label-next-reminder", metadata.next_reminder_at),
+    ];
+
+    for (label, date) in dates {
+        if let Some(date) = date {
+            println!(
+                "{}: {}",
+                messages.get(label, &[]),
+                format_date(date, absolute_dates, messages)
+            );
+        }
+    }
+}
+
+async fn print_contact_card(
+    data_repo: &Connection,
+    id: i64,
+    contact: &Contact,
+    metadata: &Metadata,
+    absolute_dates: bool,
+    messages: &Messages,
+) -> anyhow::Result<()> {
+    println!("{}", contact.display_name);
+
+    for value in [&contact.email, &contact.phone_number]
+        .into_iter()
+        .flatten()
+    {
+        println!("{value}");
+    }
+
+    print_dates(metadata, absolute_dates, *messages);
+    print_handles(data_repo, id).await?;
+
+    let last_note = data_repo
+        .get_last_interaction(id)
+        .await?
+        .and_then(|last| last.interaction.note);
+
+    if let Some(note) = last_note {
+        println!("{}: {note}", messages.get("label-last-note", &[]));
+    }
+
+    Ok(())
+}
+
+async fn log_interaction(
+    data_repo: &Connection,
+    contact_id: i64,
+    messages: &Messages,
+) -> anyhow::Result<()> {
+    let note = prompt(&messages.get("prompt-note", &[]))?;
+    let note = (!note.is_empty()).then_some(note);
+
+    let _lock = write_lock(*messages)?;
+
+    data_repo
+        .create_interaction(Interaction::new(contact_id, "reach-out", note))
+        .await?;
+
+    println!("{}", messages.get("interaction-logged", &[]));
+
+    Ok(())
+}
+
+async fn snooze(
+    data_repo: &Connection,
+    contact_id: i64,
+    absolute_dates: bool,
+    messages: &Messages,
+) -> anyhow::Result<()> {
+    let next_reminder_at = Utc::now() + Duration::days(SNOOZE_DAYS);
+
+    let _lock = write_lock(*messages)?;
+
+    data_repo
+        .update_next_reminder(contact_id, Some(next_reminder_at))
+        .await?;
+
+    let date = format_date(next_reminder_at, absolute_dates, *messages);
+
+    println!("{}", messages.get("contact-snoozed", &[("date", &date)]));
+
+    Ok(())
+}
+
+async fn print_handles(data_repo: &Connection, id: i64) -> anyhow::Result<()> {
+    let links = DeepLinks::from_env();
+
+    for handle in data_repo.get_handles_by_contact_id(id).await? {
+        match links.link(&handle) {
+            Some(link) => println!("{}: {} <{link}>", handle.platform, handle.handle),
+            None => println!("{}: {}", handle.platform, handle.handle),
         }
     }
 
     Ok(())
 }
+
+/// Renders a user's minijinja template with the contact's fields and
+/// metadata, dates as RFC 3339 strings.
+fn render_template(
+    template: &str,
+    id: i64,
+    contact: &Contact,
+    metadata: &Metadata,
+) -> anyhow::Result<String> {
+    let timestamp = |date: Option<DateTime<Utc>>| {
+        date.map(|date| date.to_rfc3339_opts(SecondsFormat::Millis, true))
+    };
+
+    let context = minijinja::context! {
+        id => id,
+        first_name => &contact.first_name,
+        last_name => &contact.last_name,
+        display_name => &contact.display_name,
+        email => &contact.email,
+        phone_number => &contact.phone_number,
+        starred => metadata.starred,
+        is_archived => metadata.is_archived,
+        cadence => &metadata.frequency,
+        created_at => timestamp(Some(metadata.created_at)),
+        updated_at => timestamp(Some(metadata.updated_at)),
+        last_seen_at => timestamp(metadata.last_seen_at),
+        next_reminder_at => timestamp(metadata.next_reminder_at),
+    };
+
+    Ok(minijinja::Environment::new().render_str(template, context)?)
+}
+
+fn print_freeform(freeform: &Freeform, messages: Messages) {
+    let name = format!("{} {}", freeform.first_name, freeform.last_name);
+
+    let fields = [
+        ("label-name", Some(name.trim())),
+        ("label-email", freeform.email.as_deref()),
+        ("label-phone", freeform.phone_number.as_deref()),
+    ];
+
+    for (label, field) in fields {
+        println!("{}: {}", messages.get(label, &[]), field.unwrap_or("-"));
+    }
+
+    for segment in &freeform.unrecognized {
+        println!("{}: {segment}", messages.get("label-unrecognized", &[]));
+    }
+}
+
+fn format_date(date: DateTime<Utc>, absolute_dates: bool, messages: Messages) -> String {
+    if absolute_dates {
+        date.to_rfc3339_opts(SecondsFormat::Millis, true)
+    } else {
+        utils::humanize(date, Utc::now(), &messages)
+    }
+}
+
+/// Days past the contact's reminder, colored by the tier they have reached,
+/// or `-` if they aren't due.
+fn days_overdue(metadata: &Metadata, now: DateTime<Utc>, tiers: &OverdueTiers) -> String {
+    let (Some(days), Some(ratio)) = (metadata.days_overdue(now), metadata.overdue_ratio(now))
+    else {
+        return "-".to_owned();
+    };
+
+    match tiers.level(ratio) {
+        Some((position, _)) => paint_tier(&days.to_string(), position),
+        None => days.to_string(),
+    }
+}
+
+fn paint_tier(text: &str, position: usize) -> String {
+    if env::var_os("NO_COLOR").is_some() || !io::stdout().is_terminal() {
+        return text.to_owned();
+    }
+
+    let color = TIER_COLORS[position.min(TIER_COLORS.len() - 1)];
+
+    format!("\x1b[{color}m{text}\x1b[0m")
+}
+
+fn prompt(question: &str) -> anyhow::Result<String> {
+    print!("{question}");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(answer.trim().to_owned())
+}
+
+/// `NBD_DATA_DIR`, or else a directory beside the database, so attachments
+/// are found whichever directory the command runs from.
+fn data_dir() -> anyhow::Result<PathBuf> {
+    match env::var("NBD_DATA_DIR") {
+        Ok(dir) => Ok(PathBuf::from(dir)),
+        Err(_) => Ok(PathBuf::from(format!("{}.data", database_file()?))),
+    }
+}
+
+/// The database file `DATABASE_URL` points at.
+fn database_file() -> anyhow::Result<String> {
+    let url = env::var("DATABASE_URL")?;
+    let file = url
+        .strip_prefix("sqlite://")
+        .or_else(|| url.strip_prefix("sqlite:"))
+        .unwrap_or(&url);
+
+    Ok(file.split('?').next().unwrap_or_default().to_owned())
+}
+
+/// The lock file beside the database in `DATABASE_URL`, so every
+/// process writing to the same database shares one lock.
+fn lock_path() -> anyhow::Result<PathBuf> {
+    Ok(PathBuf::from(format!("{}.lock", database_file()?)))
+}
+
+/// Takes the database's write lock for the rest of the caller's scope,
+/// recording the running command so anyone blocked by it can see who it is.
+fn write_lock(messages: Messages) -> anyhow::Result<WriteLock> {
+    let path = lock_path()?;
+    let command = env::args().skip(1).collect::<Vec<_>>().join(" ");
+    let holder = format!("nbd-cli {command} (pid {})", process::id());
+
+    match WriteLock::try_acquire(&path, &holder) {
+        Ok(lock) => Ok(lock),
+        Err(error) => match error.downcast_ref::<AppError>() {
+            Some(locked @ AppError::Locked(_)) => {
+                eprintln!("{}", messages.error(locked));
+
+                WriteLock::acquire(&path, &holder)
+            }
+            _ => Err(error),
+        },
+    }
+}
+
+fn open_with_default_app(target: impl AsRef<OsStr>) -> anyhow::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+
+    process::Command::new(opener).arg(target).status()?;
+
+    Ok(())
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(
+        answer.to_lowercase().as_str(),
+        "y" | "yes" | "s" | "si" | "sí"
+    )
+}
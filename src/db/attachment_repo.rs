@@ -0,0 +1,92 @@
+use crate::models;
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+
+use super::connection::Connection;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AttachmentRepo {
+    async fn create_attachment(&self, attachment: models::Attachment) -> anyhow::Result<i64>;
+    async fn get_attachments_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Vec<models::IndexedAttachment>>;
+    /// Every stored blob an attachment still refers to.
+    async fn get_attachment_hashes(&self) -> anyhow::Result<Vec<String>>;
+}
+
+#[async_trait]
+impl AttachmentRepo for Connection {
+    async fn create_attachment(&self, attachment: models::Attachment) -> anyhow::Result<i64> {
+        let query = "INSERT INTO attachments
+        (contact_id, filename, mime, size, hash, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)";
+
+        let result = sqlx::query(query)
+            .bind(attachment.contact_id)
+            .bind(&attachment.filename)
+            .bind(&attachment.mime)
+            .bind(attachment.size)
+            .bind(&attachment.hash)
+            .bind(
+                attachment
+                    .created_at
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+            )
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    async fn get_attachments_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Vec<models::IndexedAttachment>> {
+        let query = "SELECT * FROM attachments WHERE contact_id = $1 ORDER BY id";
+
+        let attachments = sqlx::query_as::<_, models::IndexedAttachment>(query)
+            .bind(contact_id)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(attachments)
+    }
+
+    async fn get_attachment_hashes(&self) -> anyhow::Result<Vec<String>> {
+        let query = "SELECT DISTINCT hash FROM attachments ORDER BY hash";
+
+        let hashes = sqlx::query_scalar::<_, String>(query)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(hashes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::*;
+
+    #[tokio::test]
+    async fn test_get_attachments_by_contact_id() {
+        let mut mock_attachment_repo = MockAttachmentRepo::new();
+
+        let attachments = vec![models::IndexedAttachment {
+            id: 1,
+            attachment: models::Attachment::new(1, "resume.pdf", 10, "abc"),
+        }];
+
+        mock_attachment_repo
+            .expect_get_attachments_by_contact_id()
+            .times(1)
+            .with(eq(1))
+            .return_once(move |_| Ok(attachments));
+
+        let result = mock_attachment_repo.get_attachments_by_contact_id(1).await;
+
+        assert_eq!(result.unwrap().len(), 1);
+    }
+}
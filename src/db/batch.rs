@@ -0,0 +1,24 @@
+use sqlx::query_builder::Separated;
+use sqlx::{QueryBuilder, Sqlite, Transaction};
+
+// Keeps each multi-row INSERT well under SQLite's bound parameter limit
+pub(crate) const BATCH_SIZE: usize = 500;
+
+/// Runs `insert` once per `BATCH_SIZE` rows, with `push_row` binding each
+/// row's values after it.
+pub(crate) async fn insert_chunked<'rows, T: Sync>(
+    transaction: &mut Transaction<'_, Sqlite>,
+    insert: &str,
+    rows: &'rows [T],
+    mut push_row: impl FnMut(Separated<'_, 'rows, Sqlite, &'static str>, &'rows T) + Send,
+) -> anyhow::Result<()> {
+    for chunk in rows.chunks(BATCH_SIZE) {
+        let mut query: QueryBuilder<'rows, Sqlite> = QueryBuilder::new(insert);
+
+        query.push_values(chunk, &mut push_row);
+
+        query.build().execute(&mut **transaction).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Content-addressed file storage, keyed by the SHA-256 of the contents.
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+impl BlobStore {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Stores `bytes` and returns their hash. Storing the same contents twice
+    /// reuses the existing file.
+    ///
+    /// # Errors
+    ///
+    /// This errors if the blob directory or file cannot be written
+    pub fn put(&self, bytes: &[u8]) -> std::io::Result<String> {
+        let hash = format!("{:x}", Sha256::digest(bytes));
+        let path = self.path(&hash);
+
+        if !path.exists() {
+            let parent = path.parent().unwrap_or(&self.root);
+            fs::create_dir_all(parent)?;
+
+            // Written beside the blob and renamed into place, so a crash
+            // never leaves a truncated file under the hash
+            let temp = parent.join(format!("{hash}.{}.tmp", std::process::id()));
+
+            if let Err(error) = fs::write(&temp, bytes).and_then(|()| fs::rename(&temp, &path)) {
+                let _ = fs::remove_file(&temp);
+
+                return Err(error);
+            }
+        }
+
+        Ok(hash)
+    }
+
+    #[must_use]
+    pub fn path(&self, hash: &str) -> PathBuf {
+        let (prefix, _) = hash.split_at(hash.len().min(2));
+
+        self.root.join(prefix).join(hash)
+    }
+
+    /// The hash of every stored blob, none if nothing has been stored yet.
+    ///
+    /// # Errors
+    ///
+    /// This errors if the blob directory cannot be read
+    pub fn hashes(&self) -> std::io::Result<Vec<String>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut hashes = Vec::new();
+
+        for prefix in fs::read_dir(&self.root)? {
+            let prefix = prefix?;
+
+            if !prefix.file_type()?.is_dir() {
+                continue;
+            }
+
+            for blob in fs::read_dir(prefix.path())? {
+                let name = blob?.file_name().to_string_lossy().into_owned();
+
+                // Leftovers of an interrupted put aren't blobs
+                if Path::new(&name).extension().is_none_or(|ext| ext != "tmp") {
+                    hashes.push(name);
+                }
+            }
+        }
+
+        hashes.sort_unstable();
+
+        Ok(hashes)
+    }
+
+    /// # Errors
+    ///
+    /// This errors if the blob exists but cannot be removed
+    pub fn remove(&self, hash: &str) -> std::io::Result<()> {
+        match fs::remove_file(self.path(hash)) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlobStore;
+    use std::fs;
+
+    #[test]
+    fn test_put_is_content_addressed() {
+        let root = std::env::temp_dir().join(format!("nbd-blobs-{}", std::process::id()));
+        let store = BlobStore::new(&root);
+
+        let hash = store.put(b"hello").unwrap();
+
+        assert_eq!(
+            hash,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(store.put(b"hello").unwrap(), hash);
+        assert_eq!(fs::read(store.path(&hash)).unwrap(), b"hello");
+        assert!(store.path(&hash).starts_with(root.join("2c")));
+        assert_eq!(fs::read_dir(root.join("2c")).unwrap().count(), 1);
+        assert_eq!(store.hashes().unwrap(), vec![hash.clone()]);
+
+        store.remove(&hash).unwrap();
+
+        assert!(store.hashes().unwrap().is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -15,9 +15,14 @@ impl Connection {
 
 #[cfg(test)]
 mod tests {
+    use chrono::{Duration, Utc};
+
     use crate::{
-        db::{fake_db::test_helpers, Connection, ContactRepo, MetadataRepo},
-        models::Contact,
+        db::{
+            fake_db::test_helpers, AttachmentRepo, Connection, ContactRepo, InteractionRepo,
+            MetadataRepo, SourceRepo,
+        },
+        models::{Attachment, Cadence, Contact, Interaction, NewContact, Source, StatusCounts},
     };
 
     #[tokio::test]
@@ -66,4 +71,318 @@ mod tests {
 
         assert!(failed_contact_metadata.is_err());
     }
+
+    #[tokio::test]
+    async fn test_create_interaction_updates_last_seen() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let example_contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+
+        let contact_id = data_repo.create_contact(example_contact).await.unwrap();
+
+        let no_interaction = data_repo.get_last_interaction(contact_id).await.unwrap();
+
+        assert!(no_interaction.is_none());
+
+        let interaction = Interaction::new(contact_id, "tea", Some("Unbirthday party".to_string()));
+
+        data_repo
+            .create_interaction(interaction.clone())
+            .await
+            .unwrap();
+
+        let last_interaction = data_repo
+            .get_last_interaction(contact_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(last_interaction.interaction.note, interaction.note);
+
+        let metadata = data_repo.get_metadata_by_id(contact_id).await.unwrap();
+
+        assert_eq!(
+            metadata.last_seen_at.map(|dt| dt.timestamp_millis()),
+            Some(interaction.occurred_at.timestamp_millis())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_attachments_by_contact() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let example_contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+
+        let contact_id = data_repo.create_contact(example_contact).await.unwrap();
+
+        let attachment = Attachment::new(contact_id, "looking-glass.pdf", 42, "abc123");
+
+        let attachment_id = data_repo
+            .create_attachment(attachment.clone())
+            .await
+            .unwrap();
+
+        let attachments = data_repo
+            .get_attachments_by_contact_id(contact_id)
+            .await
+            .unwrap();
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].id, attachment_id);
+        assert_eq!(attachments[0].attachment.filename, attachment.filename);
+        assert_eq!(attachments[0].attachment.mime, "application/pdf");
+    }
+
+    #[tokio::test]
+    async fn test_create_interaction_schedules_next_reminder() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let example_contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+
+        let contact_id = data_repo.create_contact(example_contact).await.unwrap();
+
+        data_repo
+            .update_cadence(contact_id, Some(Cadence::Monthly))
+            .await
+            .unwrap();
+
+        let interaction = Interaction::new(contact_id, "call", None);
+
+        data_repo
+            .create_interaction(interaction.clone())
+            .await
+            .unwrap();
+
+        let metadata = data_repo.get_metadata_by_id(contact_id).await.unwrap();
+
+        assert_eq!(
+            metadata.next_reminder_at.map(|dt| dt.timestamp_millis()),
+            Some((interaction.occurred_at + Cadence::Monthly.duration()).timestamp_millis())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_all_with_metadata() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let lewis =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+        let alice =
+            Contact::new("Alice", "Liddell", "alice@wonderland.com", "777-777-7778").unwrap();
+
+        let lewis_id = data_repo.create_contact(lewis.clone()).await.unwrap();
+        let alice_id = data_repo.create_contact(alice.clone()).await.unwrap();
+
+        data_repo.update_archived(alice_id, true).await.unwrap();
+
+        let contacts = data_repo.get_all_with_metadata().await.unwrap();
+
+        assert_eq!(contacts.len(), 2);
+
+        assert_eq!(contacts[0].id, lewis_id);
+        assert_eq!(contacts[0].contact, lewis);
+        assert_eq!(contacts[0].metadata.contact_id, lewis_id);
+        assert!(!contacts[0].metadata.is_archived);
+
+        assert_eq!(contacts[1].id, alice_id);
+        assert_eq!(contacts[1].contact, alice);
+        assert!(contacts[1].metadata.is_archived);
+    }
+
+    #[tokio::test]
+    async fn test_create_contacts_in_batches() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let contacts: Vec<Contact> = (0..1201)
+            .map(|index| {
+                Contact::new(
+                    &format!("Card{index}"),
+                    "Soldier",
+                    "card@wonderland.com",
+                    "777-777-7777",
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let contact_ids = data_repo.create_contacts(contacts.clone()).await.unwrap();
+
+        assert_eq!(contact_ids.len(), contacts.len());
+
+        let saved = data_repo.get_all_with_metadata().await.unwrap();
+
+        assert_eq!(saved.len(), contacts.len());
+
+        for ((contact_id, contact), saved) in contact_ids.iter().zip(&contacts).zip(&saved) {
+            assert_eq!(saved.id, *contact_id);
+            assert_eq!(&saved.contact, contact);
+            assert_eq!(saved.metadata.contact_id, *contact_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_contacts_from_source() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+        let imported = NewContact {
+            external_id: Some("people/c1".to_string()),
+            ..NewContact::from(contact.clone())
+        };
+
+        let contact_ids = data_repo
+            .create_contacts_from_source("google", vec![imported])
+            .await
+            .unwrap();
+
+        let source = data_repo
+            .get_source_by_contact_id(contact_ids[0])
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(source.source, "google");
+        assert_eq!(source.external_id, Some("people/c1".to_string()));
+        assert!(data_repo.get_metadata_by_id(contact_ids[0]).await.is_ok());
+
+        // A failed source insert takes the contacts down with it
+        sqlx::query("DROP TABLE sources")
+            .execute(&*data_repo.sqlite_pool)
+            .await
+            .unwrap();
+
+        assert!(data_repo
+            .create_contacts_from_source("manual", vec![NewContact::from(contact)])
+            .await
+            .is_err());
+        assert_eq!(data_repo.get_all_contacts().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_activity() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let lewis =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+        let alice =
+            Contact::new("Alice", "Liddell", "alice@wonderland.com", "777-777-7778").unwrap();
+
+        let mut tea = Interaction::new(0, "tea", None);
+        let mut call = Interaction::new(0, "call", None);
+        tea.occurred_at -= Duration::days(1);
+        call.occurred_at -= Duration::days(3);
+
+        let lewis = NewContact {
+            interactions: vec![tea.clone(), call],
+            ..NewContact::from(lewis)
+        };
+
+        let lewis_id = data_repo
+            .create_contacts_from_source("seed:1", vec![lewis])
+            .await
+            .unwrap()[0];
+        data_repo.create_contact(alice).await.unwrap();
+
+        let activity = data_repo.get_activity().await.unwrap();
+
+        assert_eq!(activity.len(), 1);
+        assert_eq!(activity[0].contact_id, lewis_id);
+        assert_eq!(activity[0].last_kind, "tea");
+        assert_eq!(activity[0].interactions, 2);
+        assert_eq!(
+            activity[0].last_occurred_at.timestamp_millis(),
+            tea.occurred_at.timestamp_millis()
+        );
+
+        // Recorded history leaves the contact's schedule alone
+        let metadata = data_repo.get_metadata_by_id(lewis_id).await.unwrap();
+
+        assert_eq!(metadata.last_seen_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_status_counts() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        let data_repo = Connection::new(pool);
+
+        let now = Utc::now();
+        let contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+
+        let ids = data_repo
+            .create_contacts(vec![contact.clone(), contact.clone(), contact])
+            .await
+            .unwrap();
+
+        data_repo
+            .update_next_reminder(ids[0], Some(now - Duration::days(1)))
+            .await
+            .unwrap();
+        data_repo
+            .update_next_reminder(ids[1], Some(now + Duration::days(3)))
+            .await
+            .unwrap();
+        data_repo
+            .update_next_reminder(ids[2], Some(now - Duration::days(1)))
+            .await
+            .unwrap();
+        data_repo.update_archived(ids[2], true).await.unwrap();
+        data_repo
+            .create_source(Source::new(ids[1], "csv:friends.csv", None))
+            .await
+            .unwrap();
+
+        let counts = data_repo
+            .get_status_counts(now, now + Duration::days(7), None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            counts,
+            StatusCounts {
+                contacts: 2,
+                due: 1,
+                upcoming: 1,
+                starred: 0,
+                archived: 1,
+            }
+        );
+
+        // status and due agree on who is due
+        let due = data_repo
+            .get_all_with_metadata()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|row| row.metadata.is_due(now))
+            .count();
+
+        assert_eq!(usize::try_from(counts.due).unwrap(), due);
+
+        let counts = data_repo
+            .get_status_counts(now, now, Some("csv:friends.csv".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!((counts.contacts, counts.due, counts.upcoming), (1, 0, 0));
+    }
 }
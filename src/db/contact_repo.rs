@@ -1,13 +1,33 @@
 use crate::models;
 use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::{QueryBuilder, Sqlite, Transaction};
 
-use super::{connection::Connection, MetadataRepo};
+use super::batch::{insert_chunked, BATCH_SIZE};
+use super::{connection::Connection, group_repo::GROUP_FREQUENCIES, MetadataRepo};
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait ContactRepo {
     async fn create_contact(&self, contact: models::Contact) -> anyhow::Result<i64>;
+    async fn create_contacts(&self, contacts: Vec<models::Contact>) -> anyhow::Result<Vec<i64>>;
+    /// Like `create_contacts`, but with the metadata to store for each
+    /// contact. The metadata's `contact_id` is ignored.
+    async fn create_contacts_with_metadata(
+        &self,
+        contacts: Vec<(models::Contact, models::Metadata)>,
+    ) -> anyhow::Result<Vec<i64>>;
+    /// Saves the contacts, their metadata, where they came from and their
+    /// past interactions in one transaction, so a failure leaves none of
+    /// them behind. The interactions are history, so unlike
+    /// `create_interaction` they leave last seen and reminders alone.
+    async fn create_contacts_from_source(
+        &self,
+        source: &str,
+        contacts: Vec<models::NewContact>,
+    ) -> anyhow::Result<Vec<i64>>;
     async fn get_all_contacts(&self) -> anyhow::Result<Vec<models::IndexedContact>>;
+    async fn get_all_with_metadata(&self) -> anyhow::Result<Vec<models::ContactWithMetadata>>;
     async fn update_contact(&self, update: models::ContactBuilder) -> anyhow::Result<()>;
     async fn get_contact_by_id(&self, id: i64) -> anyhow::Result<models::IndexedContact>;
     async fn delete_contact_by_id(&self, id: i64) -> anyhow::Result<i64>;
@@ -36,6 +56,93 @@ impl ContactRepo for Connection {
         Ok(contact_id)
     }
 
+    async fn create_contacts(&self, contacts: Vec<models::Contact>) -> anyhow::Result<Vec<i64>> {
+        let contacts = contacts
+            .into_iter()
+            .map(|contact| (contact, models::Metadata::new(0)))
+            .collect();
+
+        self.create_contacts_with_metadata(contacts).await
+    }
+
+    async fn create_contacts_with_metadata(
+        &self,
+        contacts: Vec<(models::Contact, models::Metadata)>,
+    ) -> anyhow::Result<Vec<i64>> {
+        let contacts: Vec<models::NewContact> = contacts
+            .into_iter()
+            .map(|(contact, metadata)| models::NewContact {
+                metadata,
+                ..models::NewContact::from(contact)
+            })
+            .collect();
+
+        let mut transaction = self.sqlite_pool.begin().await?;
+
+        let contact_ids = insert_contacts(&mut transaction, &contacts).await?;
+
+        transaction.commit().await?;
+
+        Ok(contact_ids)
+    }
+
+    async fn create_contacts_from_source(
+        &self,
+        source: &str,
+        contacts: Vec<models::NewContact>,
+    ) -> anyhow::Result<Vec<i64>> {
+        let mut transaction = self.sqlite_pool.begin().await?;
+
+        let contact_ids = insert_contacts(&mut transaction, &contacts).await?;
+
+        let created_at = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let rows: Vec<_> = contact_ids.iter().zip(&contacts).collect();
+
+        insert_chunked(
+            &mut transaction,
+            "INSERT INTO sources (contact_id, source, external_id, created_at) ",
+            &rows,
+            |mut row, (contact_id, new)| {
+                row.push_bind(**contact_id)
+                    .push_bind(source)
+                    .push_bind(&new.external_id)
+                    .push_bind(&created_at);
+            },
+        )
+        .await?;
+
+        let interactions: Vec<_> = contact_ids
+            .iter()
+            .zip(&contacts)
+            .flat_map(|(contact_id, new)| {
+                new.interactions
+                    .iter()
+                    .map(move |interaction| (*contact_id, interaction))
+            })
+            .collect();
+
+        insert_chunked(
+            &mut transaction,
+            "INSERT INTO interactions (contact_id, kind, note, occurred_at) ",
+            &interactions,
+            |mut row, (contact_id, interaction)| {
+                row.push_bind(*contact_id)
+                    .push_bind(&interaction.kind)
+                    .push_bind(&interaction.note)
+                    .push_bind(
+                        interaction
+                            .occurred_at
+                            .to_rfc3339_opts(SecondsFormat::Millis, true),
+                    );
+            },
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(contact_ids)
+    }
+
     async fn get_all_contacts(&self) -> anyhow::Result<Vec<models::IndexedContact>> {
         let get_contacts_query =
             "SELECT id, first_name, last_name, display_name, email, phone_number
@@ -50,29 +157,60 @@ impl ContactRepo for Connection {
         Ok(contacts_with_id)
     }
 
-    async fn update_contact(&self, contact: models::ContactBuilder) -> anyhow::Result<()> {
-        sqlx::query!(
-            r#"
-            UPDATE contacts
-            SET
-                first_name = COALESCE($1, first_name),
-                last_name = COALESCE($2, last_name),
-                display_name = COALESCE($3, display_name),
-                email = COALESCE($4, email),
-                phone_number = COALESCE($5, phone_number)
-            WHERE id = $6
-            "#,
-            contact.update.first_name,
-            contact.update.last_name,
-            contact.update.display_name,
-            contact.update.email,
-            contact.update.phone_number,
-            contact.id
-        )
-        .execute(&*self.sqlite_pool)
-        .await?;
+    async fn get_all_with_metadata(&self) -> anyhow::Result<Vec<models::ContactWithMetadata>> {
+        let query = format!(
+            "SELECT
+                contacts.id,
+                contacts.first_name,
+                contacts.last_name,
+                contacts.display_name,
+                contacts.email,
+                contacts.phone_number,
+                contacts_metadata.contact_id,
+                contacts_metadata.starred,
+                contacts_metadata.is_archived,
+                contacts_metadata.created_at,
+                contacts_metadata.updated_at,
+                contacts_metadata.last_seen_at,
+                contacts_metadata.next_reminder_at,
+                contacts_metadata.frequency,
+                contacts_metadata.last_reminder_at,
+                {GROUP_FREQUENCIES}
+             FROM contacts
+             JOIN contacts_metadata ON contacts_metadata.contact_id = contacts.id
+             ORDER BY contacts.id"
+        );
+
+        let contacts = sqlx::query_as::<_, models::ContactWithMetadata>(&query)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
 
-        println!("Contact updated");
+        Ok(contacts)
+    }
+
+    async fn update_contact(&self, contact: models::ContactBuilder) -> anyhow::Result<()> {
+        let mut query: QueryBuilder<Sqlite> = QueryBuilder::new("UPDATE contacts SET ");
+        let mut assignments = query.separated(", ");
+
+        for (field, field_update) in contact.update.fields() {
+            match field_update {
+                models::FieldUpdate::Keep => {}
+                models::FieldUpdate::Set(value) => {
+                    assignments.push(format!("{} = ", field.column()));
+                    assignments.push_bind_unseparated(value.clone());
+                }
+                models::FieldUpdate::Clear if field.is_nullable() => {
+                    assignments.push(format!("{} = NULL", field.column()));
+                }
+                models::FieldUpdate::Clear => {
+                    assignments.push(format!("{} = ''", field.column()));
+                }
+            }
+        }
+
+        query.push(" WHERE id = ").push_bind(contact.id);
+
+        query.build().execute(&*self.sqlite_pool).await?;
 
         Ok(())
     }
@@ -101,6 +239,68 @@ impl ContactRepo for Connection {
     }
 }
 
+/// Inserts the contacts and their metadata in `BATCH_SIZE` chunks, returning
+/// their ids in order.
+async fn insert_contacts(
+    transaction: &mut Transaction<'_, Sqlite>,
+    contacts: &[models::NewContact],
+) -> anyhow::Result<Vec<i64>> {
+    let mut contact_ids = Vec::with_capacity(contacts.len());
+
+    for chunk in contacts.chunks(BATCH_SIZE) {
+        let mut insert_contacts: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "INSERT INTO contacts (first_name, last_name, display_name, email, phone_number) ",
+        );
+
+        insert_contacts.push_values(chunk, |mut row, new| {
+            row.push_bind(&new.contact.first_name)
+                .push_bind(&new.contact.last_name)
+                .push_bind(&new.contact.display_name)
+                .push_bind(&new.contact.email)
+                .push_bind(&new.contact.phone_number);
+        });
+
+        insert_contacts.push(" RETURNING id");
+
+        // RETURNING does not guarantee row order, but ids are assigned in
+        // VALUES order, so sorting restores it
+        let mut chunk_ids: Vec<i64> = insert_contacts
+            .build_query_scalar()
+            .fetch_all(&mut **transaction)
+            .await?;
+
+        chunk_ids.sort_unstable();
+
+        let format = |date: DateTime<Utc>| date.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+        let mut insert_metadata: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "INSERT INTO contacts_metadata
+            (contact_id, starred, is_archived, frequency, created_at, updated_at,
+             last_seen_at, next_reminder_at, last_reminder_at) ",
+        );
+
+        insert_metadata.push_values(chunk_ids.iter().zip(chunk), |mut row, (contact_id, new)| {
+            let metadata = &new.metadata;
+
+            row.push_bind(*contact_id)
+                .push_bind(metadata.starred)
+                .push_bind(metadata.is_archived)
+                .push_bind(metadata.frequency.clone())
+                .push_bind(format(metadata.created_at))
+                .push_bind(format(metadata.updated_at))
+                .push_bind(metadata.last_seen_at.map(format))
+                .push_bind(metadata.next_reminder_at.map(format))
+                .push_bind(metadata.last_reminder_at.map(format));
+        });
+
+        insert_metadata.build().execute(&mut **transaction).await?;
+
+        contact_ids.extend(chunk_ids);
+    }
+
+    Ok(contact_ids)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +326,26 @@ mod tests {
         assert_eq!(result, 1);
     }
 
+    #[tokio::test]
+    async fn test_create_contacts() {
+        let mut mock_contact_repo = MockContactRepo::new();
+
+        let contacts = vec![
+            models::Contact::new("John", "Smith", "johndoe@example.com", "123-456-7890").unwrap(),
+            models::Contact::new("Jane", "Smith", "janedoe@example.com", "123-456-7891").unwrap(),
+        ];
+
+        mock_contact_repo
+            .expect_create_contacts()
+            .times(1)
+            .with(eq(contacts.clone()))
+            .returning(|_| Ok(vec![1, 2]));
+
+        let result = mock_contact_repo.create_contacts(contacts).await;
+
+        assert_eq!(result.unwrap(), vec![1, 2]);
+    }
+
     #[tokio::test]
     async fn test_get_all_contacts() {
         let mut mock_contact_repo = MockContactRepo::new();
@@ -146,6 +366,27 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_get_all_with_metadata() {
+        let mut mock_contact_repo = MockContactRepo::new();
+
+        let contacts = vec![models::ContactWithMetadata {
+            id: 1,
+            contact: models::Contact::new("John", "Doe", "johndoe@example.com", "1234567890")
+                .unwrap(),
+            metadata: models::Metadata::new(1),
+        }];
+
+        mock_contact_repo
+            .expect_get_all_with_metadata()
+            .times(1)
+            .return_once(move || Ok(contacts));
+
+        let result = mock_contact_repo.get_all_with_metadata().await.unwrap();
+
+        assert_eq!(result[0].id, result[0].metadata.contact_id);
+    }
+
     #[tokio::test]
     async fn test_update_contact() {
         let mut mock_contact_repo = MockContactRepo::new();
@@ -162,6 +403,7 @@ mod tests {
             Some("some@email.com".to_string()),
             None,
             None,
+            &[],
         )
         .unwrap();
 
@@ -61,7 +61,7 @@ mod tests {
 
     #[test]
     fn current_number_of_migrations() {
-        let migrations = 3;
+        let migrations = 10;
 
         let entries = get_migration_entries().unwrap();
 
@@ -93,34 +93,42 @@ phone_number TEXT NOT NULL
 
     #[test]
     fn read_last_sql_file_in_migrations() {
-        let expect_migration = r#"-- Add migration script here
--- Enable foreign key support
+        let expect_migration = r#"-- no-transaction
+-- Add migration script here
+-- Email and phone number become optional. SQLite cannot drop NOT NULL in
+-- place, so the table is rebuilt, with foreign keys off so that dropping the
+-- old table does not cascade to the rows that reference it.
 PRAGMA foreign_keys = OFF;
 
--- Create a temporary table for contacts_metadata
-CREATE TABLE contacts_metadata_temp (
-    contact_id INTEGER PRIMARY KEY,
-    starred BOOLEAN NOT NULL,
-    is_archived BOOLEAN NOT NULL,
-    created_at TEXT NOT NULL,
-    updated_at TEXT NOT NULL,
-    last_seen_at TEXT,
-    next_reminder_at TEXT,
-    frequency INTEGER,
-    last_reminder_at TEXT,
-    FOREIGN KEY (contact_id) REFERENCES contacts(id) ON DELETE CASCADE
+BEGIN;
+
+CREATE TABLE contacts_new
+(
+id INTEGER PRIMARY KEY AUTOINCREMENT,
+first_name TEXT NOT NULL,
+last_name TEXT NOT NULL,
+display_name TEXT NOT NULL,
+email TEXT,
+phone_number TEXT
 );
 
--- Copy data from the old table to the new one
-INSERT INTO contacts_metadata_temp SELECT * FROM contacts_metadata;
+INSERT INTO contacts_new (id, first_name, last_name, display_name, email, phone_number)
+SELECT id, first_name, last_name, display_name, NULLIF(email, ''), NULLIF(phone_number, '')
+FROM contacts;
+
+-- Keep ids of deleted contacts from being reused. contacts_new only has a
+-- sequence row if it got rows, so replace it with a copy of the old one
+DELETE FROM sqlite_sequence WHERE name = 'contacts_new';
+
+INSERT INTO sqlite_sequence (name, seq)
+SELECT 'contacts_new', seq FROM sqlite_sequence WHERE name = 'contacts';
+
+DROP TABLE contacts;
 
--- Drop the old table
-DROP TABLE contacts_metadata;
+ALTER TABLE contacts_new RENAME TO contacts;
 
--- Rename the new table to the original name
-ALTER TABLE contacts_metadata_temp RENAME TO contacts_metadata;
+COMMIT;
 
--- Re-enable foreign key support
 PRAGMA foreign_keys = ON;"#;
 
         let file_path = last_sql_snapshot().unwrap();
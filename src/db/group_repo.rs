@@ -0,0 +1,167 @@
+use crate::models;
+use crate::utils::AppError;
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+
+use super::connection::Connection;
+
+/// Selects the space separated cadences of a `contacts_metadata` row's
+/// groups, which `Metadata::cadence` falls back on when the contact has none
+/// of their own. Every query that loads metadata includes it.
+pub const GROUP_FREQUENCIES: &str = "(SELECT group_concat(contact_groups.frequency, ' ')
+    FROM contact_groups
+    JOIN contact_group_members ON contact_group_members.group_id = contact_groups.id
+    WHERE contact_group_members.contact_id = contacts_metadata.contact_id) AS group_frequencies";
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GroupRepo {
+    async fn create_group(&self, group: models::Group) -> anyhow::Result<i64>;
+    async fn get_group_by_name(&self, name: &str) -> anyhow::Result<Option<models::IndexedGroup>>;
+    async fn get_groups_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Vec<models::IndexedGroup>>;
+    async fn add_member(&self, group_id: i64, contact_id: i64) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+impl GroupRepo for Connection {
+    async fn create_group(&self, group: models::Group) -> anyhow::Result<i64> {
+        let query = "INSERT INTO contact_groups (name, frequency, created_at) VALUES (?, ?, ?)";
+
+        let result = sqlx::query(query)
+            .bind(&group.name)
+            .bind(&group.frequency)
+            .bind(
+                group
+                    .created_at
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+            )
+            .execute(&*self.sqlite_pool)
+            .await
+            .map_err(|error| match error.as_database_error() {
+                Some(database_error) if database_error.is_unique_violation() => {
+                    anyhow::Error::new(AppError::DuplicateGroup(group.name.clone()))
+                }
+                _ => error.into(),
+            })?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    async fn get_group_by_name(&self, name: &str) -> anyhow::Result<Option<models::IndexedGroup>> {
+        let query = "SELECT * FROM contact_groups WHERE name = $1";
+
+        let group = sqlx::query_as::<_, models::IndexedGroup>(query)
+            .bind(name.trim())
+            .fetch_optional(&*self.sqlite_pool)
+            .await?;
+
+        Ok(group)
+    }
+
+    async fn get_groups_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Vec<models::IndexedGroup>> {
+        let query = "SELECT contact_groups.* FROM contact_groups
+            JOIN contact_group_members ON contact_group_members.group_id = contact_groups.id
+            WHERE contact_group_members.contact_id = $1
+            ORDER BY contact_groups.name";
+
+        let groups = sqlx::query_as::<_, models::IndexedGroup>(query)
+            .bind(contact_id)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(groups)
+    }
+
+    async fn add_member(&self, group_id: i64, contact_id: i64) -> anyhow::Result<()> {
+        let query = "INSERT OR IGNORE INTO contact_group_members (group_id, contact_id)
+            VALUES (?, ?)";
+
+        sqlx::query(query)
+            .bind(group_id)
+            .bind(contact_id)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fake_db::test_helpers;
+    use crate::db::{ContactRepo, MetadataRepo};
+    use crate::models::{Cadence, Contact, Group};
+
+    #[tokio::test]
+    async fn test_members_inherit_shortest_group_cadence() {
+        let pool = test_helpers::setup_in_memory_db().await;
+        let repo = Connection::new(pool);
+
+        let contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+        let contact_id = repo.create_contact(contact).await.unwrap();
+
+        let monthly = repo
+            .create_group(Group::new("close friends", Some(Cadence::Monthly)).unwrap())
+            .await
+            .unwrap();
+        let weekly = repo
+            .create_group(Group::new("family", Some(Cadence::Weekly)).unwrap())
+            .await
+            .unwrap();
+
+        repo.add_member(monthly, contact_id).await.unwrap();
+        repo.add_member(monthly, contact_id).await.unwrap();
+
+        let metadata = repo.get_metadata_by_id(contact_id).await.unwrap();
+        assert_eq!(metadata.cadence(), Some(Cadence::Monthly));
+
+        repo.add_member(weekly, contact_id).await.unwrap();
+
+        let metadata = repo.get_metadata_by_id(contact_id).await.unwrap();
+        assert_eq!(metadata.cadence(), Some(Cadence::Weekly));
+
+        // A contact's own cadence overrides their groups'
+        repo.update_cadence(contact_id, Some(Cadence::Yearly))
+            .await
+            .unwrap();
+
+        let metadata = repo.get_metadata_by_id(contact_id).await.unwrap();
+        assert_eq!(metadata.cadence(), Some(Cadence::Yearly));
+
+        let rows = repo.get_all_with_metadata().await.unwrap();
+        let mut group_frequencies: Vec<&str> = rows[0]
+            .metadata
+            .group_frequencies
+            .as_deref()
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        group_frequencies.sort_unstable();
+        assert_eq!(group_frequencies, vec!["monthly", "weekly"]);
+
+        let groups = repo.get_groups_by_contact_id(contact_id).await.unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            repo.get_group_by_name("family").await.unwrap().unwrap().id,
+            weekly
+        );
+
+        let duplicate = repo
+            .create_group(Group::new("family", None).unwrap())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            duplicate.downcast_ref::<AppError>(),
+            Some(AppError::DuplicateGroup(name)) if name == "family"
+        ));
+    }
+}
@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+
+use super::connection::Connection;
+
+/// Tables whose rows belong to a contact and are meaningless without one.
+pub const CONTACT_TABLES: [&str; 7] = [
+    "contacts_metadata",
+    "interactions",
+    "attachments",
+    "review_items",
+    "messenger_handles",
+    "sources",
+    "contact_group_members",
+];
+
+/// Checks that back `doctor`, and the repairs it can make safely.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait HealthRepo {
+    async fn integrity_check(&self) -> anyhow::Result<Vec<String>>;
+    async fn get_schema_version(&self) -> anyhow::Result<Option<i64>>;
+    async fn count_orphans(&self, table: &'static str) -> anyhow::Result<i64>;
+    async fn get_contact_ids_missing_metadata(&self) -> anyhow::Result<Vec<i64>>;
+    async fn delete_orphans(&self) -> anyhow::Result<u64>;
+    async fn create_missing_metadata(&self) -> anyhow::Result<u64>;
+}
+
+#[async_trait]
+impl HealthRepo for Connection {
+    async fn integrity_check(&self) -> anyhow::Result<Vec<String>> {
+        let problems = sqlx::query_scalar::<_, String>("PRAGMA integrity_check")
+            .fetch_all(&*self.sqlite_pool)
+            .await?
+            .into_iter()
+            .filter(|row| row != "ok")
+            .collect();
+
+        Ok(problems)
+    }
+
+    async fn get_schema_version(&self) -> anyhow::Result<Option<i64>> {
+        let has_history = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master
+            WHERE type = 'table' AND name = '_sqlx_migrations'",
+        )
+        .fetch_one(&*self.sqlite_pool)
+        .await?;
+
+        if has_history == 0 {
+            return Ok(None);
+        }
+
+        let version = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1",
+        )
+        .fetch_one(&*self.sqlite_pool)
+        .await?;
+
+        Ok(version)
+    }
+
+    async fn count_orphans(&self, table: &'static str) -> anyhow::Result<i64> {
+        let query = format!(
+            "SELECT COUNT(*) FROM {table}
+            WHERE contact_id NOT IN (SELECT id FROM contacts)"
+        );
+
+        let count = sqlx::query_scalar::<_, i64>(&query)
+            .fetch_one(&*self.sqlite_pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    async fn get_contact_ids_missing_metadata(&self) -> anyhow::Result<Vec<i64>> {
+        let query = "SELECT id FROM contacts
+            WHERE id NOT IN (SELECT contact_id FROM contacts_metadata)
+            ORDER BY id";
+
+        let contact_ids = sqlx::query_scalar::<_, i64>(query)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(contact_ids)
+    }
+
+    async fn delete_orphans(&self) -> anyhow::Result<u64> {
+        let mut transaction = self.sqlite_pool.begin().await?;
+        let mut deleted = 0;
+
+        for table in CONTACT_TABLES {
+            let query = format!(
+                "DELETE FROM {table}
+                WHERE contact_id NOT IN (SELECT id FROM contacts)"
+            );
+
+            deleted += sqlx::query(&query)
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected();
+        }
+
+        transaction.commit().await?;
+
+        Ok(deleted)
+    }
+
+    async fn create_missing_metadata(&self) -> anyhow::Result<u64> {
+        let query = "INSERT INTO contacts_metadata
+            (contact_id, starred, is_archived, created_at, updated_at)
+            SELECT id, 0, 0, $1, $1 FROM contacts
+            WHERE id NOT IN (SELECT contact_id FROM contacts_metadata)";
+
+        let result = sqlx::query(query)
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fake_db::test_helpers;
+    use crate::db::{ContactRepo, MetadataRepo};
+    use crate::models::Contact;
+
+    #[tokio::test]
+    async fn test_healthy_database() {
+        let pool = test_helpers::setup_in_memory_db().await;
+        let repo = Connection::new(pool);
+
+        let contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+        repo.create_contact(contact).await.unwrap();
+
+        assert!(repo.integrity_check().await.unwrap().is_empty());
+        assert_eq!(repo.get_schema_version().await.unwrap(), None);
+
+        for table in CONTACT_TABLES {
+            assert_eq!(repo.count_orphans(table).await.unwrap(), 0);
+        }
+
+        assert!(repo
+            .get_contact_ids_missing_metadata()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_repairs_orphans_and_missing_metadata() {
+        let pool = test_helpers::setup_in_memory_db().await;
+
+        // Write the broken rows on one connection with foreign keys off, the
+        // way an older build or a manual edit could have left them
+        let mut connection = pool.acquire().await.unwrap();
+
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *connection)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO interactions (contact_id, kind, occurred_at)
+            VALUES (999, 'reach-out', '2024-01-01T00:00:00.000Z')",
+        )
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO contacts (first_name, last_name, display_name, email, phone_number)
+            VALUES ('Lewis', 'Carroll', 'Lewis Carroll', '', '')",
+        )
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+        drop(connection);
+
+        let repo = Connection::new(pool);
+
+        assert_eq!(repo.count_orphans("interactions").await.unwrap(), 1);
+
+        let missing = repo.get_contact_ids_missing_metadata().await.unwrap();
+        assert_eq!(missing.len(), 1);
+
+        assert_eq!(repo.delete_orphans().await.unwrap(), 1);
+        assert_eq!(repo.create_missing_metadata().await.unwrap(), 1);
+
+        assert_eq!(repo.count_orphans("interactions").await.unwrap(), 0);
+        assert!(repo
+            .get_contact_ids_missing_metadata()
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repo.get_metadata_by_id(missing[0]).await.is_ok());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::models;
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+
+use super::{connection::Connection, MetadataRepo};
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait InteractionRepo {
+    async fn create_interaction(&self, interaction: models::Interaction) -> anyhow::Result<i64>;
+    async fn get_last_interaction(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Option<models::IndexedInteraction>>;
+    /// The latest interaction of every contact that has one.
+    async fn get_activity(&self) -> anyhow::Result<Vec<models::Activity>>;
+}
+
+#[async_trait]
+impl InteractionRepo for Connection {
+    async fn create_interaction(&self, interaction: models::Interaction) -> anyhow::Result<i64> {
+        let query = "INSERT INTO interactions
+        (contact_id, kind, note, occurred_at)
+        VALUES (?, ?, ?, ?)";
+
+        let result = sqlx::query(query)
+            .bind(interaction.contact_id)
+            .bind(&interaction.kind)
+            .bind(&interaction.note)
+            .bind(
+                interaction
+                    .occurred_at
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+            )
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        // An interaction is the contact being seen
+        self.update_last_seen(interaction.contact_id, interaction.occurred_at)
+            .await?;
+
+        let metadata = self.get_metadata_by_id(interaction.contact_id).await?;
+
+        if let Some(cadence) = metadata.cadence() {
+            let next_reminder_at = interaction.occurred_at + cadence.duration();
+
+            self.update_next_reminder(interaction.contact_id, Some(next_reminder_at))
+                .await?;
+        }
+
+        Ok(result.last_insert_rowid())
+    }
+
+    async fn get_last_interaction(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Option<models::IndexedInteraction>> {
+        let query = "SELECT * FROM interactions
+             WHERE contact_id = $1
+             ORDER BY occurred_at DESC, id DESC
+             LIMIT 1";
+
+        let interaction = sqlx::query_as::<_, models::IndexedInteraction>(query)
+            .bind(contact_id)
+            .fetch_optional(&*self.sqlite_pool)
+            .await?;
+
+        Ok(interaction)
+    }
+
+    async fn get_activity(&self) -> anyhow::Result<Vec<models::Activity>> {
+        // With a lone MAX(), SQLite takes the bare kind column from the row
+        // holding the maximum, so one pass gives the latest kind too
+        let query = "SELECT
+                contact_id,
+                kind AS last_kind,
+                MAX(occurred_at) AS last_occurred_at,
+                COUNT(*) AS interactions
+             FROM interactions
+             GROUP BY contact_id
+             ORDER BY contact_id";
+
+        let activity = sqlx::query_as::<_, models::Activity>(query)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(activity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::*;
+
+    #[tokio::test]
+    async fn test_create_interaction() {
+        let mut mock_interaction_repo = MockInteractionRepo::new();
+
+        let interaction = models::Interaction::new(1, "call", None);
+
+        mock_interaction_repo
+            .expect_create_interaction()
+            .times(1)
+            .with(eq(interaction.clone()))
+            .returning(|_| Ok(1));
+
+        let result = mock_interaction_repo.create_interaction(interaction).await;
+
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_last_interaction_none() {
+        let mut mock_interaction_repo = MockInteractionRepo::new();
+
+        mock_interaction_repo
+            .expect_get_last_interaction()
+            .times(1)
+            .with(eq(1))
+            .returning(|_| Ok(None));
+
+        let result = mock_interaction_repo.get_last_interaction(1).await;
+
+        assert!(result.unwrap().is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::utils::AppError;
+
+/// An exclusive advisory lock held while a command makes several related
+/// writes, so two processes sharing a database cannot interleave them.
+///
+/// The holder's description is written into the lock file so a blocked
+/// command can say who has it. The operating system releases the lock if
+/// the holder exits without dropping it.
+pub struct WriteLock {
+    file: File,
+}
+
+impl WriteLock {
+    /// Takes the lock, waiting for the current holder to release it.
+    ///
+    /// # Errors
+    ///
+    /// This errors if the lock file cannot be opened or written
+    pub fn acquire(path: &Path, holder: &str) -> anyhow::Result<Self> {
+        let file = open(path)?;
+
+        file.lock()?;
+
+        Self::hold(file, holder)
+    }
+
+    /// Takes the lock without waiting.
+    ///
+    /// # Errors
+    ///
+    /// This errors with `AppError::Locked` and the current holder's
+    /// description if someone else has the lock, or if the lock file cannot
+    /// be opened or written
+    pub fn try_acquire(path: &Path, holder: &str) -> anyhow::Result<Self> {
+        let mut file = open(path)?;
+
+        match file.try_lock() {
+            Ok(()) => Self::hold(file, holder),
+            Err(TryLockError::WouldBlock) => {
+                let mut current_holder = String::new();
+                file.read_to_string(&mut current_holder)?;
+
+                Err(AppError::Locked(current_holder.trim().to_owned()).into())
+            }
+            Err(TryLockError::Error(error)) => Err(error.into()),
+        }
+    }
+
+    fn hold(mut file: File, holder: &str) -> anyhow::Result<Self> {
+        file.set_len(0)?;
+        file.write_all(holder.as_bytes())?;
+        file.flush()?;
+
+        Ok(Self { file })
+    }
+}
+
+impl Drop for WriteLock {
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+fn open(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WriteLock;
+    use crate::utils::AppError;
+
+    #[test]
+    fn test_second_holder_is_blocked() {
+        let path = std::env::temp_dir().join(format!("nbd-lock-{}", std::process::id()));
+
+        let lock = WriteLock::try_acquire(&path, "review (pid 1)").unwrap();
+
+        let error = WriteLock::try_acquire(&path, "seed (pid 2)").err().unwrap();
+
+        assert!(matches!(
+            error.downcast_ref::<AppError>(),
+            Some(AppError::Locked(holder)) if holder == "review (pid 1)"
+        ));
+
+        drop(lock);
+
+        let lock = WriteLock::acquire(&path, "seed (pid 2)").unwrap();
+
+        drop(lock);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+use crate::models;
+use async_trait::async_trait;
+
+use super::connection::Connection;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait MessengerRepo {
+    async fn set_handle(&self, handle: models::MessengerHandle) -> anyhow::Result<()>;
+    async fn get_handles_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Vec<models::MessengerHandle>>;
+    async fn delete_handle(&self, contact_id: i64, platform: &str) -> anyhow::Result<bool>;
+}
+
+#[async_trait]
+impl MessengerRepo for Connection {
+    async fn set_handle(&self, handle: models::MessengerHandle) -> anyhow::Result<()> {
+        let query = "INSERT INTO messenger_handles (contact_id, platform, handle)
+        VALUES (?, ?, ?)
+        ON CONFLICT (contact_id, platform) DO UPDATE SET handle = excluded.handle";
+
+        sqlx::query(query)
+            .bind(handle.contact_id)
+            .bind(&handle.platform)
+            .bind(&handle.handle)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_handles_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Vec<models::MessengerHandle>> {
+        let query = "SELECT * FROM messenger_handles WHERE contact_id = $1 ORDER BY platform";
+
+        let handles = sqlx::query_as::<_, models::MessengerHandle>(query)
+            .bind(contact_id)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(handles)
+    }
+
+    async fn delete_handle(&self, contact_id: i64, platform: &str) -> anyhow::Result<bool> {
+        let query = "DELETE FROM messenger_handles WHERE contact_id = $1 AND platform = $2";
+
+        let result = sqlx::query(query)
+            .bind(contact_id)
+            .bind(platform.to_lowercase())
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fake_db::test_helpers;
+    use crate::db::ContactRepo;
+    use crate::models::{Contact, DeepLinks, MessengerHandle};
+
+    #[tokio::test]
+    async fn test_set_replaces_handle_per_platform() {
+        let pool = test_helpers::setup_in_memory_db().await;
+        let repo = Connection::new(pool);
+        let links = DeepLinks::new("");
+
+        let contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+        let contact_id = repo.create_contact(contact).await.unwrap();
+
+        let old = MessengerHandle::new(contact_id, "telegram", "@lewis", &links).unwrap();
+        let new = MessengerHandle::new(contact_id, "telegram", "@carroll", &links).unwrap();
+        let signal = MessengerHandle::new(contact_id, "signal", "777-777-7777", &links).unwrap();
+
+        repo.set_handle(old).await.unwrap();
+        repo.set_handle(new.clone()).await.unwrap();
+        repo.set_handle(signal.clone()).await.unwrap();
+
+        let handles = repo.get_handles_by_contact_id(contact_id).await.unwrap();
+
+        assert_eq!(handles, vec![signal, new]);
+
+        assert!(repo.delete_handle(contact_id, "Signal").await.unwrap());
+        assert!(!repo.delete_handle(contact_id, "signal").await.unwrap());
+        assert_eq!(
+            repo.get_handles_by_contact_id(contact_id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -1,14 +1,44 @@
 use crate::models::{self, Metadata};
 use async_trait::async_trait;
-use chrono::SecondsFormat;
+use chrono::{DateTime, SecondsFormat, Utc};
 
-use super::Connection;
+use super::{group_repo::GROUP_FREQUENCIES, Connection};
+
+/// Whether a `contacts_metadata` row is due by the time bound as `$1`, the
+/// SQL form of `Metadata::is_due`. Every query that lists or counts due
+/// contacts uses it, so `review`, `status` and `due` agree on who is due.
+const IS_DUE: &str = "(is_archived = 0 AND next_reminder_at <= $1)";
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait MetadataRepo {
     async fn create_metadata(&self, contact_id: i64) -> anyhow::Result<Metadata>;
     async fn get_metadata_by_id(&self, contact_id: i64) -> anyhow::Result<models::Metadata>;
+    async fn update_last_seen(
+        &self,
+        contact_id: i64,
+        last_seen_at: DateTime<Utc>,
+    ) -> anyhow::Result<()>;
+    async fn update_next_reminder(
+        &self,
+        contact_id: i64,
+        next_reminder_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()>;
+    async fn update_cadence(
+        &self,
+        contact_id: i64,
+        cadence: Option<models::Cadence>,
+    ) -> anyhow::Result<()>;
+    async fn update_archived(&self, contact_id: i64, is_archived: bool) -> anyhow::Result<()>;
+    async fn get_due_metadata(&self, until: DateTime<Utc>) -> anyhow::Result<Vec<Metadata>>;
+    /// Counts reminders due by `now` and upcoming by `until`, among the
+    /// contacts from `source` if given.
+    async fn get_status_counts(
+        &self,
+        now: DateTime<Utc>,
+        until: DateTime<Utc>,
+        source: Option<String>,
+    ) -> anyhow::Result<models::StatusCounts>;
 }
 
 #[async_trait]
@@ -65,15 +95,134 @@ impl MetadataRepo for Connection {
         self.get_metadata_by_id(contact_id).await
     }
     async fn get_metadata_by_id(&self, contact_id: i64) -> anyhow::Result<models::Metadata> {
-        let query_get_by_id = "SELECT * FROM contacts_metadata WHERE contact_id=$1";
+        let query_get_by_id =
+            format!("SELECT *, {GROUP_FREQUENCIES} FROM contacts_metadata WHERE contact_id=$1");
 
-        let metadata: models::Metadata = sqlx::query_as::<_, models::Metadata>(query_get_by_id)
+        let metadata: models::Metadata = sqlx::query_as::<_, models::Metadata>(&query_get_by_id)
             .bind(contact_id)
             .fetch_one(&*self.sqlite_pool)
             .await?;
 
         Ok(metadata)
     }
+
+    async fn update_last_seen(
+        &self,
+        contact_id: i64,
+        last_seen_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let query = "UPDATE contacts_metadata
+            SET last_seen_at = $1, updated_at = $2
+            WHERE contact_id = $3";
+
+        sqlx::query(query)
+            .bind(last_seen_at.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(contact_id)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_next_reminder(
+        &self,
+        contact_id: i64,
+        next_reminder_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let query = "UPDATE contacts_metadata
+            SET next_reminder_at = $1, updated_at = $2
+            WHERE contact_id = $3";
+
+        sqlx::query(query)
+            .bind(next_reminder_at.map(|dt| dt.to_rfc3339_opts(SecondsFormat::Millis, true)))
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(contact_id)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_cadence(
+        &self,
+        contact_id: i64,
+        cadence: Option<models::Cadence>,
+    ) -> anyhow::Result<()> {
+        let query = "UPDATE contacts_metadata
+            SET frequency = $1, updated_at = $2
+            WHERE contact_id = $3";
+
+        sqlx::query(query)
+            .bind(cadence.map(models::Cadence::as_str))
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(contact_id)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_archived(&self, contact_id: i64, is_archived: bool) -> anyhow::Result<()> {
+        let query = "UPDATE contacts_metadata
+            SET is_archived = $1, updated_at = $2
+            WHERE contact_id = $3";
+
+        sqlx::query(query)
+            .bind(is_archived)
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(contact_id)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_due_metadata(&self, until: DateTime<Utc>) -> anyhow::Result<Vec<Metadata>> {
+        let query = format!(
+            "SELECT *, {GROUP_FREQUENCIES} FROM contacts_metadata
+            WHERE {IS_DUE}
+            ORDER BY next_reminder_at"
+        );
+
+        let metadata = sqlx::query_as::<_, models::Metadata>(&query)
+            .bind(until.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(metadata)
+    }
+
+    async fn get_status_counts(
+        &self,
+        now: DateTime<Utc>,
+        until: DateTime<Utc>,
+        source: Option<String>,
+    ) -> anyhow::Result<models::StatusCounts> {
+        // One pass over contacts_metadata, so status stays fast enough for a
+        // shell prompt. A NULL next_reminder_at compares as NULL, which SUM skips
+        let query = format!(
+            "SELECT
+                COALESCE(SUM(is_archived = 0), 0) AS contacts,
+                COALESCE(SUM({IS_DUE}), 0) AS due,
+                COALESCE(SUM(is_archived = 0 AND next_reminder_at > $1
+                    AND next_reminder_at <= $2), 0) AS upcoming,
+                COALESCE(SUM(is_archived = 0 AND starred), 0) AS starred,
+                COALESCE(SUM(is_archived), 0) AS archived
+             FROM contacts_metadata
+             WHERE $3 IS NULL
+             OR contact_id IN (SELECT contact_id FROM sources WHERE source = $3)"
+        );
+
+        let counts = sqlx::query_as::<_, models::StatusCounts>(&query)
+            .bind(now.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(until.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(source)
+            .fetch_one(&*self.sqlite_pool)
+            .await?;
+
+        Ok(counts)
+    }
 }
 
 #[cfg(test)]
@@ -106,6 +255,23 @@ mod tests {
         .await
         .expect("Failed to create contacts_metadata table");
 
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS contact_groups (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                frequency TEXT,
+                created_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS contact_group_members (
+                group_id INTEGER NOT NULL,
+                contact_id INTEGER NOT NULL,
+                PRIMARY KEY (group_id, contact_id)
+            )",
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create group tables");
+
         pool
     }
     #[tokio::test]
@@ -120,6 +286,53 @@ mod tests {
         assert_eq!(result.contact_id, contact_id);
     }
 
+    #[tokio::test]
+    async fn test_update_next_reminder_sqlite() {
+        let pool = setup_test_db().await;
+        let repo = Connection::new(pool);
+
+        repo.create_metadata(1).await.unwrap();
+
+        let next_reminder_at = Utc::now() + chrono::Duration::days(7);
+
+        repo.update_next_reminder(1, Some(next_reminder_at))
+            .await
+            .unwrap();
+
+        let metadata = repo.get_metadata_by_id(1).await.unwrap();
+
+        assert_eq!(
+            metadata.next_reminder_at.map(|dt| dt.timestamp_millis()),
+            Some(next_reminder_at.timestamp_millis())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_due_metadata_sqlite() {
+        let pool = setup_test_db().await;
+        let repo = Connection::new(pool);
+
+        let now = Utc::now();
+
+        for contact_id in 1..=3 {
+            repo.create_metadata(contact_id).await.unwrap();
+        }
+
+        repo.update_next_reminder(1, Some(now - chrono::Duration::days(1)))
+            .await
+            .unwrap();
+        repo.update_next_reminder(2, Some(now + chrono::Duration::days(30)))
+            .await
+            .unwrap();
+        repo.update_next_reminder(3, Some(now)).await.unwrap();
+        repo.update_archived(3, true).await.unwrap();
+
+        let due = repo.get_due_metadata(now).await.unwrap();
+
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].contact_id, 1);
+    }
+
     #[tokio::test]
     async fn test_create_metadata() {
         let mut mock_metadata_repo = MockMetadataRepo::new();
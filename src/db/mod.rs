@@ -1,9 +1,28 @@
+mod attachment_repo;
+mod batch;
+mod blob_store;
 mod connection;
 mod contact_repo;
 mod fake_db;
+mod group_repo;
+mod health_repo;
+mod interaction_repo;
+mod lock;
+mod messenger_repo;
 mod metadata_repo;
+mod review_repo;
+mod source_repo;
 
+pub use blob_store::BlobStore;
 pub use connection::Connection;
+pub use lock::WriteLock;
 
+pub use attachment_repo::AttachmentRepo;
 pub use contact_repo::ContactRepo;
+pub use group_repo::GroupRepo;
+pub use health_repo::{HealthRepo, CONTACT_TABLES};
+pub use interaction_repo::InteractionRepo;
+pub use messenger_repo::MessengerRepo;
 pub use metadata_repo::MetadataRepo;
+pub use review_repo::ReviewRepo;
+pub use source_repo::SourceRepo;
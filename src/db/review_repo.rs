@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+
+use super::connection::Connection;
+
+/// Tracks which contacts have been handled in a review so an interrupted
+/// review can pick up where it left off.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ReviewRepo {
+    async fn get_open_review_id(&self) -> anyhow::Result<Option<i64>>;
+    async fn create_review(&self) -> anyhow::Result<i64>;
+    async fn get_reviewed_contact_ids(&self, review_id: i64) -> anyhow::Result<Vec<i64>>;
+    async fn mark_reviewed(&self, review_id: i64, contact_id: i64) -> anyhow::Result<()>;
+    async fn complete_review(&self, review_id: i64) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+impl ReviewRepo for Connection {
+    async fn get_open_review_id(&self) -> anyhow::Result<Option<i64>> {
+        let query = "SELECT id FROM reviews
+            WHERE completed_at IS NULL
+            ORDER BY id DESC
+            LIMIT 1";
+
+        let review_id = sqlx::query_scalar::<_, i64>(query)
+            .fetch_optional(&*self.sqlite_pool)
+            .await?;
+
+        Ok(review_id)
+    }
+
+    async fn create_review(&self) -> anyhow::Result<i64> {
+        let query = "INSERT INTO reviews (started_at) VALUES (?)";
+
+        let result = sqlx::query(query)
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    async fn get_reviewed_contact_ids(&self, review_id: i64) -> anyhow::Result<Vec<i64>> {
+        let query = "SELECT contact_id FROM review_items WHERE review_id = $1";
+
+        let contact_ids = sqlx::query_scalar::<_, i64>(query)
+            .bind(review_id)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(contact_ids)
+    }
+
+    async fn mark_reviewed(&self, review_id: i64, contact_id: i64) -> anyhow::Result<()> {
+        let query = "INSERT OR IGNORE INTO review_items
+        (review_id, contact_id, reviewed_at)
+        VALUES (?, ?, ?)";
+
+        sqlx::query(query)
+            .bind(review_id)
+            .bind(contact_id)
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn complete_review(&self, review_id: i64) -> anyhow::Result<()> {
+        let query = "UPDATE reviews SET completed_at = $1 WHERE id = $2";
+
+        sqlx::query(query)
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+            .bind(review_id)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fake_db::test_helpers;
+    use crate::db::ContactRepo;
+    use crate::models::Contact;
+
+    #[tokio::test]
+    async fn test_review_resumes_until_completed() {
+        let pool = test_helpers::setup_in_memory_db().await;
+        let repo = Connection::new(pool);
+
+        let contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+        let contact_id = repo.create_contact(contact).await.unwrap();
+
+        assert_eq!(repo.get_open_review_id().await.unwrap(), None);
+
+        let review_id = repo.create_review().await.unwrap();
+        repo.mark_reviewed(review_id, contact_id).await.unwrap();
+        repo.mark_reviewed(review_id, contact_id).await.unwrap();
+
+        assert_eq!(repo.get_open_review_id().await.unwrap(), Some(review_id));
+        assert_eq!(
+            repo.get_reviewed_contact_ids(review_id).await.unwrap(),
+            vec![contact_id]
+        );
+
+        repo.complete_review(review_id).await.unwrap();
+
+        assert_eq!(repo.get_open_review_id().await.unwrap(), None);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::models;
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+
+use super::connection::Connection;
+
+/// Records where contacts came from, so one source's contacts can be listed
+/// or removed together.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SourceRepo {
+    async fn create_source(&self, source: models::Source) -> anyhow::Result<()>;
+    async fn get_source_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Option<models::Source>>;
+    async fn get_contact_ids_by_source(&self, source: &str) -> anyhow::Result<Vec<i64>>;
+    async fn delete_contacts_by_source(&self, source: &str) -> anyhow::Result<u64>;
+}
+
+#[async_trait]
+impl SourceRepo for Connection {
+    async fn create_source(&self, source: models::Source) -> anyhow::Result<()> {
+        let query = "INSERT INTO sources (contact_id, source, external_id, created_at)
+        VALUES (?, ?, ?, ?)";
+
+        sqlx::query(query)
+            .bind(source.contact_id)
+            .bind(&source.source)
+            .bind(&source.external_id)
+            .bind(
+                source
+                    .created_at
+                    .to_rfc3339_opts(SecondsFormat::Millis, true),
+            )
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_source_by_contact_id(
+        &self,
+        contact_id: i64,
+    ) -> anyhow::Result<Option<models::Source>> {
+        let query = "SELECT * FROM sources WHERE contact_id = $1";
+
+        let source = sqlx::query_as::<_, models::Source>(query)
+            .bind(contact_id)
+            .fetch_optional(&*self.sqlite_pool)
+            .await?;
+
+        Ok(source)
+    }
+
+    async fn get_contact_ids_by_source(&self, source: &str) -> anyhow::Result<Vec<i64>> {
+        let query = "SELECT contact_id FROM sources WHERE source = $1 ORDER BY contact_id";
+
+        let contact_ids = sqlx::query_scalar::<_, i64>(query)
+            .bind(source)
+            .fetch_all(&*self.sqlite_pool)
+            .await?;
+
+        Ok(contact_ids)
+    }
+
+    async fn delete_contacts_by_source(&self, source: &str) -> anyhow::Result<u64> {
+        // Metadata, interactions and the source rows cascade with the contact
+        let query = "DELETE FROM contacts
+            WHERE id IN (SELECT contact_id FROM sources WHERE source = $1)";
+
+        let result = sqlx::query(query)
+            .bind(source)
+            .execute(&*self.sqlite_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fake_db::test_helpers;
+    use crate::db::ContactRepo;
+    use crate::models::{Contact, NewContact, Source};
+
+    #[tokio::test]
+    async fn test_rollback_removes_only_that_source() {
+        let pool = test_helpers::setup_in_memory_db().await;
+        let repo = Connection::new(pool);
+
+        let contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+
+        let manual_id = repo.create_contact(contact.clone()).await.unwrap();
+        repo.create_source(Source::manual(manual_id)).await.unwrap();
+
+        let imported = (0..2)
+            .map(|index| NewContact {
+                external_id: Some(index.to_string()),
+                ..NewContact::from(contact.clone())
+            })
+            .collect();
+        let imported_ids = repo
+            .create_contacts_from_source("csv:bad.csv", imported)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repo.get_contact_ids_by_source("csv:bad.csv").await.unwrap(),
+            imported_ids
+        );
+        assert_eq!(
+            repo.get_source_by_contact_id(imported_ids[1])
+                .await
+                .unwrap()
+                .unwrap()
+                .external_id,
+            Some("1".to_string())
+        );
+
+        assert_eq!(
+            repo.delete_contacts_by_source("csv:bad.csv").await.unwrap(),
+            2
+        );
+
+        assert_eq!(repo.get_all_contacts().await.unwrap().len(), 1);
+        assert!(repo
+            .get_contact_ids_by_source("csv:bad.csv")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            repo.get_source_by_contact_id(manual_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .source,
+            "manual"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use serde::Deserialize;
+
+use crate::models::Contact;
+use crate::utils::AppError;
+
+/// Importer executables are named with this prefix, e.g. `nbd-import-vcf`.
+const PREFIX: &str = "nbd-import-";
+
+/// One contact as printed by an importer.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Imported {
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: String,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub external_id: Option<String>,
+}
+
+impl Imported {
+    /// # Errors
+    ///
+    /// This errors if there is an invalid email or phone number, or no name
+    pub fn to_contact(&self) -> Result<Contact, AppError> {
+        Contact::new(
+            &self.first_name,
+            &self.last_name,
+            self.email.as_deref().unwrap_or(""),
+            self.phone_number.as_deref().unwrap_or(""),
+        )
+    }
+}
+
+/// An importer plugin: any executable that reads a file's bytes on stdin,
+/// gets the file name as its only argument, and prints a JSON array of
+/// contacts on stdout.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Importer {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Importer {
+    /// Where importers are looked for: `NBD_PLUGIN_DIR`, then `PATH`.
+    #[must_use]
+    pub fn search_path() -> Vec<PathBuf> {
+        let plugin_dir = env::var_os("NBD_PLUGIN_DIR").map(PathBuf::from);
+        let path = env::var_os("PATH").unwrap_or_default();
+
+        plugin_dir
+            .into_iter()
+            .chain(env::split_paths(&path))
+            .collect()
+    }
+
+    /// The first importer called `name` in `dirs`.
+    #[must_use]
+    pub fn find(name: &str, dirs: &[PathBuf]) -> Option<Self> {
+        let filename = format!("{PREFIX}{name}{}", env::consts::EXE_SUFFIX);
+
+        dirs.iter()
+            .map(|dir| dir.join(&filename))
+            .find(|path| path.is_file())
+            .map(|path| Self {
+                name: name.to_owned(),
+                path,
+            })
+    }
+
+    /// Every importer in `dirs`, the first one found winning for each name.
+    #[must_use]
+    pub fn discover(dirs: &[PathBuf]) -> Vec<Self> {
+        let mut importers: Vec<Self> = Vec::new();
+
+        for dir in dirs {
+            let Ok(entries) = dir.read_dir() else {
+                continue;
+            };
+
+            for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+                let Some(name) = importer_name(&path) else {
+                    continue;
+                };
+
+                if path.is_file() && !importers.iter().any(|known| known.name == name) {
+                    importers.push(Self { name, path });
+                }
+            }
+        }
+
+        importers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        importers
+    }
+
+    /// # Errors
+    ///
+    /// This errors with `AppError::ImporterFailed` if the importer cannot be
+    /// started, exits unsuccessfully, or prints something other than a JSON
+    /// array of contacts
+    pub fn run(&self, filename: &str, bytes: &[u8]) -> Result<Vec<Imported>, AppError> {
+        let failed = |error: String| AppError::ImporterFailed(self.name.clone(), error);
+
+        let mut child = Command::new(&self.path)
+            .arg(filename)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| failed(error.to_string()))?;
+
+        // Feed stdin from another thread so an importer that writes before it
+        // has read everything cannot deadlock against us
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| failed("stdin is not piped".to_owned()))?;
+        let bytes = bytes.to_vec();
+        let writer = thread::spawn(move || stdin.write_all(&bytes));
+
+        let output = child
+            .wait_with_output()
+            .map_err(|error| failed(error.to_string()))?;
+
+        // A broken pipe only means the importer didn't need all of its input
+        let _ = writer.join();
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+
+            return Err(failed(stderr.trim().to_owned()));
+        }
+
+        serde_json::from_slice(&output.stdout).map_err(|error| failed(error.to_string()))
+    }
+}
+
+fn importer_name(path: &Path) -> Option<String> {
+    let filename = path.file_name()?.to_str()?;
+    let name = filename.strip_prefix(PREFIX)?;
+    let name = name.strip_suffix(env::consts::EXE_SUFFIX).unwrap_or(name);
+
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Imported, Importer};
+    use crate::utils::AppError;
+
+    #[test]
+    fn test_imported_contact() {
+        let imported: Vec<Imported> = serde_json::from_str(
+            r#"[{"first_name": "Lewis", "last_name": "Carroll",
+                "email": "lewis@wonderland.com", "phone_number": "777-777-7777",
+                "external_id": "42"}]"#,
+        )
+        .unwrap();
+
+        let contact = imported[0].to_contact().unwrap();
+
+        assert_eq!(contact.display_name, "Lewis Carroll");
+        assert_eq!(imported[0].external_id.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn test_unknown_importer() {
+        let dirs = vec![std::env::temp_dir()];
+
+        assert_eq!(Importer::find("does-not-exist", &dirs), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_importer() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("nbd-importers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let echo = dir.join("nbd-import-echo");
+        std::fs::write(
+            &echo,
+            "#!/bin/sh\nprintf '[{\"first_name\": \"%s\"}]' \"$(cat)\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&echo, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let broken = dir.join("nbd-import-broken");
+        std::fs::write(&broken, "#!/bin/sh\necho 'bad header' >&2\nexit 1\n").unwrap();
+        std::fs::set_permissions(&broken, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let dirs = vec![dir.clone()];
+
+        let names: Vec<String> = Importer::discover(&dirs)
+            .into_iter()
+            .map(|importer| importer.name)
+            .collect();
+        assert_eq!(names, vec!["broken", "echo"]);
+
+        let imported = Importer::find("echo", &dirs)
+            .unwrap()
+            .run("people.txt", b"Lewis")
+            .unwrap();
+        assert_eq!(imported[0].first_name, "Lewis");
+        assert_eq!(imported[0].email, None);
+
+        let error = Importer::find("broken", &dirs)
+            .unwrap()
+            .run("people.txt", b"")
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            AppError::ImporterFailed(name, stderr) if name == "broken" && stderr == "bad header"
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,3 +1,5 @@
 pub mod db;
+pub mod importer;
 pub mod models;
+pub mod seed;
 pub mod utils;
@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+
+/// A contact's interaction history boiled down to its latest entry.
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Activity {
+    pub contact_id: i64,
+    pub last_kind: String,
+    pub last_occurred_at: DateTime<Utc>,
+    pub interactions: i64,
+}
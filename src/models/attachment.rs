@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+
+use crate::utils;
+
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Attachment {
+    pub contact_id: i64,
+    pub filename: String,
+    pub mime: String,
+    pub size: i64,
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Indexed {
+    pub id: i64,
+    #[sqlx(flatten)]
+    pub attachment: Attachment,
+}
+
+impl Attachment {
+    #[must_use]
+    pub fn new(contact_id: i64, filename: &str, size: i64, hash: &str) -> Self {
+        Self {
+            contact_id,
+            filename: filename.to_owned(),
+            mime: utils::guess_mime(filename).to_owned(),
+            size,
+            hash: hash.to_owned(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Attachment;
+
+    #[test]
+    fn test_attachment_guesses_mime() {
+        let attachment = Attachment::new(1, "resume.pdf", 1024, "abc123");
+
+        assert_eq!(attachment.mime, "application/pdf");
+        assert_eq!(attachment.size, 1024);
+        assert_eq!(attachment.hash, "abc123");
+    }
+}
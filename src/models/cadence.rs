@@ -0,0 +1,103 @@
+use chrono::Duration;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::utils::AppError;
+
+/// How often a contact should be reached out to, stored as
+/// `contacts_metadata.frequency`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cadence {
+    Weekly,
+    Biweekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl Cadence {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::Biweekly => "biweekly",
+            Self::Monthly => "monthly",
+            Self::Quarterly => "quarterly",
+            Self::Yearly => "yearly",
+        }
+    }
+
+    #[must_use]
+    pub const fn days(self) -> i64 {
+        match self {
+            Self::Weekly => 7,
+            Self::Biweekly => 14,
+            Self::Monthly => 30,
+            Self::Quarterly => 91,
+            Self::Yearly => 365,
+        }
+    }
+
+    #[must_use]
+    pub const fn duration(self) -> Duration {
+        Duration::days(self.days())
+    }
+}
+
+impl FromStr for Cadence {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "weekly" => Ok(Self::Weekly),
+            "biweekly" => Ok(Self::Biweekly),
+            "monthly" => Ok(Self::Monthly),
+            "quarterly" => Ok(Self::Quarterly),
+            "yearly" => Ok(Self::Yearly),
+            _ => Err(AppError::InvalidCadence(value.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Cadence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cadence;
+    use crate::utils::AppError;
+
+    #[test]
+    fn test_parse_cadence() {
+        assert_eq!("monthly".parse::<Cadence>().unwrap(), Cadence::Monthly);
+        assert_eq!(
+            " Quarterly ".parse::<Cadence>().unwrap(),
+            Cadence::Quarterly
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_cadence() {
+        let result = "fortnightly".parse::<Cadence>();
+
+        assert!(
+            matches!(result, Err(AppError::InvalidCadence(cadence)) if cadence == "fortnightly")
+        );
+    }
+
+    #[test]
+    fn test_cadence_round_trip() {
+        for cadence in [
+            Cadence::Weekly,
+            Cadence::Biweekly,
+            Cadence::Monthly,
+            Cadence::Quarterly,
+            Cadence::Yearly,
+        ] {
+            assert_eq!(cadence.to_string().parse::<Cadence>().unwrap(), cadence);
+        }
+    }
+}
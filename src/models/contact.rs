@@ -1,13 +1,18 @@
+use std::fmt;
+use std::str::FromStr;
+
 use crate::utils;
 use crate::utils::AppError;
 
+use super::{Interaction, Metadata};
+
 #[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
 pub struct Contact {
     pub first_name: String,
     pub last_name: String,
     pub display_name: String,
-    pub email: String,
-    pub phone_number: String,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
@@ -17,22 +22,177 @@ pub struct Indexed {
     pub contact: Contact,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct WithMetadata {
+    pub id: i64,
+    #[sqlx(flatten)]
+    pub contact: Contact,
+    #[sqlx(flatten)]
+    pub metadata: Metadata,
+}
+
+/// A contact to save, with the metadata to start it with, its id in the
+/// system it came from and any past interactions. The `contact_id` of the
+/// metadata and interactions is ignored.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct New {
+    pub contact: Contact,
+    pub metadata: Metadata,
+    pub external_id: Option<String>,
+    pub interactions: Vec<Interaction>,
+}
+
+/// Fresh metadata, no external id and no history, as for a contact typed in
+/// by hand.
+impl From<Contact> for New {
+    fn from(contact: Contact) -> Self {
+        Self {
+            contact,
+            metadata: Metadata::new(0),
+            external_id: None,
+            interactions: Vec::new(),
+        }
+    }
+}
+
+/// A contact column that an update can set or clear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    FirstName,
+    LastName,
+    DisplayName,
+    Email,
+    PhoneNumber,
+}
+
+impl Field {
+    #[must_use]
+    pub const fn column(self) -> &'static str {
+        match self {
+            Self::FirstName => "first_name",
+            Self::LastName => "last_name",
+            Self::DisplayName => "display_name",
+            Self::Email => "email",
+            Self::PhoneNumber => "phone_number",
+        }
+    }
+
+    const fn is_name(self) -> bool {
+        matches!(self, Self::FirstName | Self::LastName | Self::DisplayName)
+    }
+
+    /// Whether clearing the field stores NULL rather than an empty string.
+    #[must_use]
+    pub const fn is_nullable(self) -> bool {
+        !self.is_name()
+    }
+}
+
+impl FromStr for Field {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "first-name" | "first" => Ok(Self::FirstName),
+            "last-name" | "last" => Ok(Self::LastName),
+            "display-name" | "display" => Ok(Self::DisplayName),
+            "email" => Ok(Self::Email),
+            "phone-number" | "phone" => Ok(Self::PhoneNumber),
+            _ => Err(AppError::UnknownField(value.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.column())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub enum FieldUpdate<T> {
+    #[default]
+    Keep,
+    Set(T),
+    Clear,
+}
+
+impl<T> FieldUpdate<T> {
+    #[must_use]
+    pub const fn is_keep(&self) -> bool {
+        matches!(self, Self::Keep)
+    }
+
+    #[must_use]
+    pub const fn is_clear(&self) -> bool {
+        matches!(self, Self::Clear)
+    }
+
+    #[must_use]
+    pub const fn as_set(&self) -> Option<&T> {
+        match self {
+            Self::Set(value) => Some(value),
+            Self::Keep | Self::Clear => None,
+        }
+    }
+}
+
+impl FieldUpdate<String> {
+    /// The value after the update, given the `current` value.
+    #[must_use]
+    pub fn or_current<'a>(&'a self, current: &'a str) -> &'a str {
+        match self {
+            Self::Keep => current,
+            Self::Set(value) => value,
+            Self::Clear => "",
+        }
+    }
+}
+
+impl<T> From<Option<T>> for FieldUpdate<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Keep, Self::Set)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Update {
-    pub first_name: Option<String>,
-    pub last_name: Option<String>,
-    pub display_name: Option<String>,
-    pub email: Option<String>,
-    pub phone_number: Option<String>,
+    pub first_name: FieldUpdate<String>,
+    pub last_name: FieldUpdate<String>,
+    pub display_name: FieldUpdate<String>,
+    pub email: FieldUpdate<String>,
+    pub phone_number: FieldUpdate<String>,
 }
 
 impl Update {
+    #[must_use]
     pub const fn is_empty(&self) -> bool {
-        self.first_name.is_none()
-            && self.last_name.is_none()
-            && self.display_name.is_none()
-            && self.email.is_none()
-            && self.phone_number.is_none()
+        self.first_name.is_keep()
+            && self.last_name.is_keep()
+            && self.display_name.is_keep()
+            && self.email.is_keep()
+            && self.phone_number.is_keep()
+    }
+
+    #[must_use]
+    pub const fn fields(&self) -> [(Field, &FieldUpdate<String>); 5] {
+        [
+            (Field::FirstName, &self.first_name),
+            (Field::LastName, &self.last_name),
+            (Field::DisplayName, &self.display_name),
+            (Field::Email, &self.email),
+            (Field::PhoneNumber, &self.phone_number),
+        ]
+    }
+
+    const fn field_mut(&mut self, field: Field) -> &mut FieldUpdate<String> {
+        match field {
+            Field::FirstName => &mut self.first_name,
+            Field::LastName => &mut self.last_name,
+            Field::DisplayName => &mut self.display_name,
+            Field::Email => &mut self.email,
+            Field::PhoneNumber => &mut self.phone_number,
+        }
     }
 }
 
@@ -42,9 +202,13 @@ pub struct Construct {
     pub update: Update,
 }
 impl Construct {
+    /// Fields given as `Some` are set, fields listed in `clear` are cleared,
+    /// and everything else is kept.
+    ///
     /// # Errors
     ///
-    /// This errors if there is an invalid email or phone number
+    /// This errors if there is an invalid email or phone number, a blank or
+    /// cleared name, a field that is both set and cleared, or nothing to update
     pub fn new(
         id: i64,
         first_name: Option<String>,
@@ -52,28 +216,47 @@ impl Construct {
         email: Option<String>,
         phone_number: Option<String>,
         display_name: Option<String>,
+        clear: &[Field],
     ) -> Result<Self, AppError> {
-        let maybe_email = email.as_deref().unwrap_or("");
+        let mut update = Update {
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+            display_name: display_name.into(),
+            email: email.into(),
+            phone_number: phone_number.into(),
+        };
 
-        if utils::is_not_valid_email(maybe_email) && Option::is_some(&email) {
-            return Err(AppError::InvalidEmail(email.clone().unwrap_or_default()));
-        }
+        for field in clear {
+            if field.is_name() {
+                return Err(AppError::BlankField(field.column().to_owned()));
+            }
+
+            let field_update = update.field_mut(*field);
 
-        let maybe_phone = phone_number.as_deref().unwrap_or("");
+            if field_update.as_set().is_some() {
+                return Err(AppError::ConflictingUpdate(field.column().to_owned()));
+            }
 
-        if utils::is_not_valid_phone_number(maybe_phone) && Option::is_some(&phone_number) {
-            return Err(AppError::InvalidPhoneNumber(
-                phone_number.clone().unwrap_or_default(),
-            ));
+            *field_update = FieldUpdate::Clear;
         }
 
-        let update = Update {
-            first_name,
-            last_name,
-            display_name,
-            email,
-            phone_number,
-        };
+        for (field, field_update) in update.fields() {
+            let Some(value) = field_update.as_set() else {
+                continue;
+            };
+
+            if field.is_name() && value.trim().is_empty() {
+                return Err(AppError::BlankField(field.column().to_owned()));
+            }
+
+            if field == Field::Email && utils::is_not_valid_email(value) {
+                return Err(AppError::InvalidEmail(value.clone()));
+            }
+
+            if field == Field::PhoneNumber && utils::is_not_valid_phone_number(value) {
+                return Err(AppError::InvalidPhoneNumber(value.clone()));
+            }
+        }
 
         if update.is_empty() {
             return Err(AppError::EmptyUpdate);
@@ -82,57 +265,144 @@ impl Construct {
         Ok(Self { id, update })
     }
 
-    #[allow(dead_code)]
-    const fn is_empty(&self) -> bool {
-        self.update.first_name.is_none()
-            && self.update.last_name.is_none()
-            && self.update.display_name.is_none()
-            && self.update.email.is_none()
-            && self.update.phone_number.is_none()
+    /// True when a first or last name changes without a new display name,
+    /// meaning the stored display name may now be stale.
+    #[must_use]
+    pub const fn changes_name(&self) -> bool {
+        (!self.update.first_name.is_keep() || !self.update.last_name.is_keep())
+            && self.update.display_name.is_keep()
+    }
+
+    /// The display name `current` would get from the updated first and last
+    /// names, the same way `Contact::new` builds it.
+    #[must_use]
+    pub fn recomputed_display_name(&self, current: &Contact) -> String {
+        let first_name = self.update.first_name.or_current(&current.first_name);
+        let last_name = self.update.last_name.or_current(&current.last_name);
+
+        format!("{first_name} {last_name}").trim().to_owned()
     }
 }
 
 impl Contact {
+    /// An empty email or phone number is left unset. Whether a contact needs
+    /// them is up to `RequiredFields`.
+    ///
     /// # Errors
     ///
-    /// This errors if there is an invalid email or phone number
+    /// This errors if there is an invalid email or phone number, or neither a
+    /// first nor a last name
     pub fn new(
         first_name: &str,
         last_name: &str,
         email: &str,
         phone_number: &str,
     ) -> Result<Self, AppError> {
-        let display_name = format!("{first_name} {last_name}");
+        let display_name = format!("{first_name} {last_name}").trim().to_owned();
 
-        if utils::is_not_valid_email(email) {
-            return Err(AppError::InvalidEmail(email.to_owned()));
+        if display_name.is_empty() {
+            return Err(AppError::BlankField(Field::DisplayName.column().to_owned()));
         }
 
-        if utils::is_not_valid_phone_number(phone_number) {
-            return Err(AppError::InvalidPhoneNumber(phone_number.to_owned()));
-        }
+        let email = (!email.trim().is_empty()).then(|| email.to_owned());
+        let phone_number = (!phone_number.trim().is_empty()).then(|| phone_number.to_owned());
 
-        Ok(Self {
+        let contact = Self {
             first_name: first_name.to_owned(),
             last_name: last_name.to_owned(),
             display_name,
-            email: email.to_owned(),
-            phone_number: phone_number.to_owned(),
-        })
+            email,
+            phone_number,
+        };
+
+        contact.validate_email_and_phone()?;
+
+        Ok(contact)
+    }
+
+    /// The field's value, `None` if it is unset or blank.
+    #[must_use]
+    pub fn field(&self, field: Field) -> Option<&str> {
+        let value = match field {
+            Field::FirstName => Some(self.first_name.as_str()),
+            Field::LastName => Some(self.last_name.as_str()),
+            Field::DisplayName => Some(self.display_name.as_str()),
+            Field::Email => self.email.as_deref(),
+            Field::PhoneNumber => self.phone_number.as_deref(),
+        };
+
+        value.filter(|value| !value.trim().is_empty())
+    }
+
+    /// Checks a stored contact against the current rules. Email and phone
+    /// may be unset, but must be valid if set.
+    ///
+    /// # Errors
+    ///
+    /// This errors if the display name is blank or the email or phone number
+    /// is invalid
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.field(Field::DisplayName).is_none() {
+            return Err(AppError::BlankField(Field::DisplayName.column().to_owned()));
+        }
+
+        self.validate_email_and_phone()
+    }
+
+    fn validate_email_and_phone(&self) -> Result<(), AppError> {
+        if let Some(email) = self.field(Field::Email) {
+            if utils::is_not_valid_email(email) {
+                return Err(AppError::InvalidEmail(email.to_owned()));
+            }
+        }
+
+        if let Some(phone_number) = self.field(Field::PhoneNumber) {
+            if utils::is_not_valid_phone_number(phone_number) {
+                return Err(AppError::InvalidPhoneNumber(phone_number.to_owned()));
+            }
+        }
+
+        Ok(())
     }
 }
 #[cfg(test)]
 mod tests {
     use crate::utils::AppError;
 
-    use super::{Construct, Contact};
+    use super::{Construct, Contact, Field, FieldUpdate};
 
     #[test]
     fn test_display_name() {
         let person = Contact::new("Jason", "Ribble", "john@example.com", "123-456-7890");
         let display_name = "Jason Ribble".to_string();
 
-        assert_eq!(person.unwrap().display_name, display_name)
+        assert_eq!(person.unwrap().display_name, display_name);
+
+        let first_name_only = Contact::new("Jason", "", "", "").unwrap();
+
+        assert_eq!(first_name_only.display_name, "Jason");
+
+        assert!(matches!(
+            Contact::new(" ", "", "john@example.com", ""),
+            Err(AppError::BlankField(field)) if field == "display_name"
+        ));
+    }
+
+    #[test]
+    fn test_email_and_phone_are_optional() {
+        let contact = Contact::new("Lewis", "Carroll", "", "777-777-7777").unwrap();
+
+        assert_eq!(contact.email, None);
+        assert_eq!(contact.field(Field::Email), None);
+        assert_eq!(contact.field(Field::PhoneNumber), Some("777-777-7777"));
+
+        let contact = Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "").unwrap();
+
+        assert_eq!(contact.phone_number, None);
+        assert!(matches!(
+            Contact::new("Lewis", "Carroll", "lewis@", ""),
+            Err(AppError::InvalidEmail(email)) if email == "lewis@"
+        ));
     }
 
     #[test]
@@ -144,15 +414,22 @@ mod tests {
             None,
             Some("123-233-1221".to_string()),
             Some("Nickname".to_string()),
+            &[],
         )
         .unwrap();
 
         assert_eq!(edits.id, 1);
-        assert_eq!(edits.update.display_name, Some("Nickname".to_string()));
-        assert_eq!(edits.update.phone_number, Some("123-233-1221".to_string()));
-        assert_eq!(edits.update.first_name, None);
-        assert_eq!(edits.update.last_name, None);
-        assert_eq!(edits.update.email, None);
+        assert_eq!(
+            edits.update.display_name,
+            FieldUpdate::Set("Nickname".to_string())
+        );
+        assert_eq!(
+            edits.update.phone_number,
+            FieldUpdate::Set("123-233-1221".to_string())
+        );
+        assert_eq!(edits.update.first_name, FieldUpdate::Keep);
+        assert_eq!(edits.update.last_name, FieldUpdate::Keep);
+        assert_eq!(edits.update.email, FieldUpdate::Keep);
     }
 
     #[test]
@@ -164,23 +441,102 @@ mod tests {
             Some("new@email.com".to_string()),
             None,
             None,
+            &[],
         )
         .unwrap();
 
         assert_eq!(edits.id, 2);
-        assert_eq!(edits.update.first_name, Some("Mary".to_string()));
-        assert_eq!(edits.update.last_name, Some("Smith".to_string()));
-        assert_eq!(edits.update.email, Some("new@email.com".to_string()));
-        assert_eq!(edits.update.phone_number, None);
-        assert_eq!(edits.update.display_name, None);
+        assert_eq!(
+            edits.update.first_name,
+            FieldUpdate::Set("Mary".to_string())
+        );
+        assert_eq!(
+            edits.update.last_name,
+            FieldUpdate::Set("Smith".to_string())
+        );
+        assert_eq!(
+            edits.update.email,
+            FieldUpdate::Set("new@email.com".to_string())
+        );
+        assert_eq!(edits.update.phone_number, FieldUpdate::Keep);
+        assert_eq!(edits.update.display_name, FieldUpdate::Keep);
     }
 
     #[test]
     fn test_is_empty() {
-        let result = Construct::new(1, None, None, None, None, None);
+        let result = Construct::new(1, None, None, None, None, None, &[]);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_blank_name_construct() {
+        let result = Construct::new(1, Some("  ".to_string()), None, None, None, None, &[]);
+
+        assert!(matches!(result, Err(AppError::BlankField(field)) if field == "first_name"));
+
+        let result = Construct::new(1, None, None, None, None, Some(String::new()), &[]);
+
+        assert!(matches!(result, Err(AppError::BlankField(field)) if field == "display_name"));
+    }
+
+    #[test]
+    fn test_changes_name() {
+        let rename =
+            Construct::new(1, None, Some("Dodgson".to_string()), None, None, None, &[]).unwrap();
+
+        assert!(rename.changes_name());
+
+        let rename_with_display_name = Construct::new(
+            1,
+            None,
+            Some("Dodgson".to_string()),
+            None,
+            None,
+            Some("Lewis Carroll".to_string()),
+            &[],
+        )
+        .unwrap();
+
+        assert!(!rename_with_display_name.changes_name());
+
+        let new_email = Construct::new(
+            1,
+            None,
+            None,
+            Some("new@email.com".to_string()),
+            None,
+            None,
+            &[],
+        )
+        .unwrap();
+
+        assert!(!new_email.changes_name());
+    }
+
+    #[test]
+    fn test_recomputed_display_name() {
+        let current =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+
+        let edits = Construct::new(
+            1,
+            Some("Charles".to_string()),
+            Some("Dodgson".to_string()),
+            None,
+            None,
+            None,
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(edits.recomputed_display_name(&current), "Charles Dodgson");
+
+        let edits =
+            Construct::new(1, Some("Charles".to_string()), None, None, None, None, &[]).unwrap();
+
+        assert_eq!(edits.recomputed_display_name(&current), "Charles Carroll");
+    }
+
     #[test]
     fn test_invalid_email_construct() {
         let result = Construct::new(
@@ -190,6 +546,7 @@ mod tests {
             Some("invalid@example".to_string()),
             None,
             None,
+            &[],
         );
         assert!(result.is_err());
         assert!(matches!(result, Err(AppError::InvalidEmail(email)) if email == "invalid@example"));
@@ -197,7 +554,15 @@ mod tests {
 
     #[test]
     fn test_invalid_construct_phone_number() {
-        let result = Construct::new(1, None, None, None, Some("123-123-12345".to_string()), None);
+        let result = Construct::new(
+            1,
+            None,
+            None,
+            None,
+            Some("123-123-12345".to_string()),
+            None,
+            &[],
+        );
 
         println!("{result:?}");
         assert!(result.is_err());
@@ -206,4 +571,65 @@ mod tests {
             matches!(result, Err(AppError::InvalidPhoneNumber(phone_number)) if phone_number == "123-123-12345")
         );
     }
+
+    #[test]
+    fn test_clear_field_construct() {
+        let edits = Construct::new(1, None, None, None, None, None, &[Field::PhoneNumber]).unwrap();
+
+        assert_eq!(edits.update.phone_number, FieldUpdate::Clear);
+        assert_eq!(edits.update.email, FieldUpdate::Keep);
+
+        let result = Construct::new(1, None, None, None, None, None, &[Field::FirstName]);
+
+        assert!(matches!(result, Err(AppError::BlankField(field)) if field == "first_name"));
+    }
+
+    #[test]
+    fn test_set_and_clear_conflict() {
+        let result = Construct::new(
+            1,
+            None,
+            None,
+            Some("new@email.com".to_string()),
+            None,
+            None,
+            &[Field::Email],
+        );
+
+        assert!(matches!(result, Err(AppError::ConflictingUpdate(field)) if field == "email"));
+    }
+
+    #[test]
+    fn test_parse_field() {
+        assert_eq!("phone".parse::<Field>().unwrap(), Field::PhoneNumber);
+        assert_eq!("first_name".parse::<Field>().unwrap(), Field::FirstName);
+        assert_eq!("Display-Name".parse::<Field>().unwrap(), Field::DisplayName);
+        assert!(matches!(
+            "nickname".parse::<Field>(),
+            Err(AppError::UnknownField(field)) if field == "nickname"
+        ));
+    }
+
+    #[test]
+    fn test_validate_stored_contact() {
+        let mut contact =
+            Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "777-777-7777").unwrap();
+
+        assert!(contact.validate().is_ok());
+
+        contact.phone_number = None;
+        assert!(contact.validate().is_ok());
+
+        contact.email = Some("lewis@".to_string());
+        assert!(matches!(
+            contact.validate(),
+            Err(AppError::InvalidEmail(email)) if email == "lewis@"
+        ));
+
+        contact.display_name = " ".to_string();
+        assert!(matches!(
+            contact.validate(),
+            Err(AppError::BlankField(field)) if field == "display_name"
+        ));
+    }
 }
@@ -0,0 +1,108 @@
+use crate::utils;
+use crate::utils::AppError;
+
+use super::Contact;
+
+/// A contact heuristically split out of a pasted line such as
+/// `Jane Doe, jane@acme.com, +1 415 555 0100, Acme Corp`.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Freeform {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub unrecognized: Vec<String>,
+}
+
+impl Freeform {
+    /// Segments are separated by commas, semicolons or newlines. The first
+    /// valid email and phone number are taken as such, the first remaining
+    /// segment is the name, and anything else is kept as unrecognized.
+    #[must_use]
+    pub fn parse(line: &str) -> Self {
+        let mut freeform = Self::default();
+
+        let segments = line
+            .split([',', ';', '\n'])
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty());
+
+        for segment in segments {
+            if freeform.email.is_none() && !utils::is_not_valid_email(segment) {
+                freeform.email = Some(segment.to_owned());
+            } else if freeform.phone_number.is_none() && !utils::is_not_valid_phone_number(segment)
+            {
+                freeform.phone_number = Some(segment.to_owned());
+            } else if freeform.first_name.is_empty() {
+                let (first_name, last_name) = segment
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((segment, ""));
+
+                first_name.clone_into(&mut freeform.first_name);
+                last_name.trim().clone_into(&mut freeform.last_name);
+            } else {
+                freeform.unrecognized.push(segment.to_owned());
+            }
+        }
+
+        freeform
+    }
+
+    /// # Errors
+    ///
+    /// This errors if there is an invalid email or phone number, or no name
+    pub fn into_contact(self) -> Result<Contact, AppError> {
+        Contact::new(
+            &self.first_name,
+            &self.last_name,
+            self.email.as_deref().unwrap_or(""),
+            self.phone_number.as_deref().unwrap_or(""),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Freeform;
+
+    #[test]
+    fn test_parse_business_card_line() {
+        let freeform = Freeform::parse("Jane Doe, jane@acme.com, +1 415 555 0100, Acme Corp");
+
+        assert_eq!(freeform.first_name, "Jane");
+        assert_eq!(freeform.last_name, "Doe");
+        assert_eq!(freeform.email, Some("jane@acme.com".to_string()));
+        assert_eq!(freeform.phone_number, Some("+1 415 555 0100".to_string()));
+        assert_eq!(freeform.unrecognized, vec!["Acme Corp".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_any_order() {
+        let freeform = Freeform::parse("415-555-0100; jane@acme.com; Jane van Doe");
+
+        assert_eq!(freeform.first_name, "Jane");
+        assert_eq!(freeform.last_name, "van Doe");
+        assert_eq!(freeform.email, Some("jane@acme.com".to_string()));
+        assert_eq!(freeform.phone_number, Some("415-555-0100".to_string()));
+        assert!(freeform.unrecognized.is_empty());
+    }
+
+    #[test]
+    fn test_into_contact() {
+        let contact = Freeform::parse("Jane Doe, jane@acme.com, 415-555-0100")
+            .into_contact()
+            .unwrap();
+
+        assert_eq!(contact.display_name, "Jane Doe");
+    }
+
+    #[test]
+    fn test_into_contact_without_email() {
+        let contact = Freeform::parse("Jane Doe, 415-555-0100")
+            .into_contact()
+            .unwrap();
+
+        assert_eq!(contact.email, None);
+        assert_eq!(contact.phone_number, Some("415-555-0100".to_string()));
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+
+use crate::utils::AppError;
+
+use super::Cadence;
+
+/// A named set of contacts whose cadence applies to every member without a
+/// cadence of their own.
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Group {
+    pub name: String,
+    pub frequency: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Indexed {
+    pub id: i64,
+    #[sqlx(flatten)]
+    pub group: Group,
+}
+
+impl Group {
+    /// # Errors
+    ///
+    /// This errors if the name is blank
+    pub fn new(name: &str, cadence: Option<Cadence>) -> Result<Self, AppError> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(AppError::BlankField("name".to_owned()));
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            frequency: cadence.map(|cadence| cadence.as_str().to_owned()),
+            created_at: Utc::now(),
+        })
+    }
+
+    #[must_use]
+    pub fn cadence(&self) -> Option<Cadence> {
+        self.frequency
+            .as_deref()
+            .and_then(|frequency| frequency.parse().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cadence, Group};
+    use crate::utils::AppError;
+
+    #[test]
+    fn test_new_group() {
+        let group = Group::new(" close friends ", Some(Cadence::Monthly)).unwrap();
+
+        assert_eq!(group.name, "close friends");
+        assert_eq!(group.cadence(), Some(Cadence::Monthly));
+        assert!(matches!(
+            Group::new(" ", None),
+            Err(AppError::BlankField(field)) if field == "name"
+        ));
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Interaction {
+    pub contact_id: i64,
+    pub kind: String,
+    pub note: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Indexed {
+    pub id: i64,
+    #[sqlx(flatten)]
+    pub interaction: Interaction,
+}
+
+impl Interaction {
+    #[must_use]
+    pub fn new(contact_id: i64, kind: &str, note: Option<String>) -> Self {
+        Self {
+            contact_id,
+            kind: kind.to_owned(),
+            note,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::Interaction;
+
+    #[test]
+    fn test_new_interaction_occurs_now() {
+        let interaction = Interaction::new(1, "call", Some("Caught up".to_string()));
+
+        assert_eq!(interaction.contact_id, 1);
+        assert_eq!(interaction.kind, "call");
+        assert_eq!(interaction.note, Some("Caught up".to_string()));
+        assert!(Utc::now() - interaction.occurred_at < Duration::milliseconds(500));
+    }
+}
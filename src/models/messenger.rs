@@ -0,0 +1,213 @@
+use std::env;
+
+use crate::utils::AppError;
+
+/// Where a link template takes the contact's normalized handle.
+const PLACEHOLDER: &str = "{handle}";
+
+/// Link templates for the messengers that work out of the box. `{handle}`
+/// is replaced with the contact's normalized handle.
+const BUILT_IN: [(&str, &str); 3] = [
+    ("signal", "https://signal.me/#p/{handle}"),
+    ("telegram", "https://t.me/{handle}"),
+    ("whatsapp", "https://wa.me/{handle}"),
+];
+
+/// A contact's account on a messenger, e.g. their Telegram username.
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Handle {
+    pub contact_id: i64,
+    pub platform: String,
+    pub handle: String,
+}
+
+impl Handle {
+    /// Normalizes the handle the way the platform's links expect it: phone
+    /// numbers for Signal and `WhatsApp`, usernames without `@` for Telegram.
+    ///
+    /// # Errors
+    ///
+    /// This errors if there is no link template for the platform, the handle
+    /// is blank, or a Signal or `WhatsApp` handle has no digits
+    pub fn new(
+        contact_id: i64,
+        platform: &str,
+        handle: &str,
+        links: &DeepLinks,
+    ) -> Result<Self, AppError> {
+        let platform = platform.trim().to_lowercase();
+        let handle = handle.trim();
+
+        if links.template(&platform).is_none() {
+            return Err(AppError::UnknownMessenger(platform));
+        }
+
+        if handle.is_empty() {
+            return Err(AppError::BlankHandle(platform));
+        }
+
+        let handle = match platform.as_str() {
+            "signal" | "whatsapp" => {
+                let digits: String = handle.chars().filter(char::is_ascii_digit).collect();
+
+                if digits.is_empty() {
+                    return Err(AppError::InvalidPhoneNumber(handle.to_owned()));
+                }
+
+                if platform == "signal" {
+                    format!("+{digits}")
+                } else {
+                    digits
+                }
+            }
+            "telegram" => handle.trim_start_matches('@').to_owned(),
+            _ => handle.to_owned(),
+        };
+
+        Ok(Self {
+            contact_id,
+            platform,
+            handle,
+        })
+    }
+}
+
+/// Deep link templates by platform, the built-in ones plus any from
+/// `NBD_MESSENGER_TEMPLATES`.
+#[derive(Debug, Clone)]
+pub struct DeepLinks {
+    templates: Vec<(String, String)>,
+}
+
+impl DeepLinks {
+    /// Reads extra templates from `NBD_MESSENGER_TEMPLATES`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::new(&env::var("NBD_MESSENGER_TEMPLATES").unwrap_or_default())
+    }
+
+    /// Parses whitespace separated `platform=template` pairs, e.g.
+    /// `matrix=https://matrix.to/#/{handle}`. A pair naming a built-in
+    /// platform replaces its template.
+    #[must_use]
+    pub fn new(custom: &str) -> Self {
+        let mut templates: Vec<(String, String)> = BUILT_IN
+            .iter()
+            .map(|(platform, template)| ((*platform).to_owned(), (*template).to_owned()))
+            .collect();
+
+        for pair in custom.split_whitespace() {
+            let Some((platform, template)) = pair.split_once('=') else {
+                continue;
+            };
+
+            let platform = platform.to_lowercase();
+
+            templates.retain(|(known, _)| *known != platform);
+            templates.push((platform, template.to_owned()));
+        }
+
+        Self { templates }
+    }
+
+    #[must_use]
+    pub fn template(&self, platform: &str) -> Option<&str> {
+        self.templates
+            .iter()
+            .find(|(known, _)| known == platform)
+            .map(|(_, template)| template.as_str())
+    }
+
+    /// The link that opens a chat with `handle`, if its platform still has
+    /// a template. The handle is percent-encoded, so a custom handle can't
+    /// add its own path or query to the link.
+    #[must_use]
+    pub fn link(&self, handle: &Handle) -> Option<String> {
+        self.template(&handle.platform)
+            .map(|template| template.replace(PLACEHOLDER, &percent_encode(&handle.handle)))
+    }
+}
+
+/// Encodes every byte but URL unreserved characters and the `@`, `:` and
+/// `+` that usernames and phone numbers use.
+fn percent_encode(handle: &str) -> String {
+    handle
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'@'
+            | b':'
+            | b'+' => char::from(byte).to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeepLinks, Handle};
+    use crate::utils::AppError;
+
+    #[test]
+    fn test_built_in_links() {
+        let links = DeepLinks::new("");
+
+        let signal = Handle::new(1, "Signal", "+1 (415) 555-0100", &links).unwrap();
+        let whatsapp = Handle::new(1, "whatsapp", "+1 415 555 0100", &links).unwrap();
+        let telegram = Handle::new(1, "telegram", "@lewis", &links).unwrap();
+
+        assert_eq!(
+            links.link(&signal).unwrap(),
+            "https://signal.me/#p/+14155550100"
+        );
+        assert_eq!(links.link(&whatsapp).unwrap(), "https://wa.me/14155550100");
+        assert_eq!(links.link(&telegram).unwrap(), "https://t.me/lewis");
+    }
+
+    #[test]
+    fn test_custom_templates() {
+        let links = DeepLinks::new(
+            "matrix=https://matrix.to/#/{handle} telegram=tg://resolve?domain={handle}",
+        );
+
+        let matrix = Handle::new(1, "matrix", "@lewis:matrix.org", &links).unwrap();
+        let telegram = Handle::new(1, "telegram", "lewis", &links).unwrap();
+
+        assert_eq!(
+            links.link(&matrix).unwrap(),
+            "https://matrix.to/#/@lewis:matrix.org"
+        );
+        assert_eq!(links.link(&telegram).unwrap(), "tg://resolve?domain=lewis");
+
+        let matrix = Handle::new(1, "matrix", "lewis/../?x=1 é", &links).unwrap();
+
+        assert_eq!(
+            links.link(&matrix).unwrap(),
+            "https://matrix.to/#/lewis%2F..%2F%3Fx%3D1%20%C3%A9"
+        );
+    }
+
+    #[test]
+    fn test_invalid_handles() {
+        let links = DeepLinks::new("");
+
+        assert!(matches!(
+            Handle::new(1, "icq", "123", &links),
+            Err(AppError::UnknownMessenger(platform)) if platform == "icq"
+        ));
+        assert!(matches!(
+            Handle::new(1, "whatsapp", "lewis", &links),
+            Err(AppError::InvalidPhoneNumber(_))
+        ));
+        assert!(matches!(
+            Handle::new(1, "telegram", " ", &links),
+            Err(AppError::BlankHandle(platform)) if platform == "telegram"
+        ));
+    }
+}
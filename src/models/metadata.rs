@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
 
+use super::Cadence;
+
 #[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
 pub struct Metadata {
     pub contact_id: i64,
@@ -11,6 +13,10 @@ pub struct Metadata {
     pub next_reminder_at: Option<DateTime<Utc>>,
     pub frequency: Option<String>,
     pub last_reminder_at: Option<DateTime<Utc>>,
+    /// Cadences of the contact's groups, space separated. Loaded alongside
+    /// the row rather than stored in it.
+    #[sqlx(default)]
+    pub group_frequencies: Option<String>,
 }
 
 impl Metadata {
@@ -28,15 +34,78 @@ impl Metadata {
             next_reminder_at: None,
             frequency: None,
             last_reminder_at: None,
+            group_frequencies: None,
         }
     }
+
+    /// Whole days since the contact was last seen, or since they were added
+    /// if they have never been seen.
+    #[must_use]
+    pub fn days_since_seen(&self, now: DateTime<Utc>) -> i64 {
+        let seen_at = self.last_seen_at.unwrap_or(self.created_at);
+
+        (now - seen_at).num_days().max(0)
+    }
+
+    /// The contact's own cadence, or else the most frequent of their groups'.
+    #[must_use]
+    pub fn cadence(&self) -> Option<Cadence> {
+        self.frequency
+            .as_deref()
+            .and_then(|frequency| frequency.parse().ok())
+            .or_else(|| {
+                self.group_frequencies
+                    .as_deref()?
+                    .split_whitespace()
+                    .filter_map(|frequency| frequency.parse::<Cadence>().ok())
+                    .min_by_key(|cadence| cadence.days())
+            })
+    }
+
+    /// Whether the contact's reminder has come, the same test `review` and
+    /// `status` make. Archived contacts are never due.
+    #[must_use]
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        !self.is_archived
+            && self
+                .next_reminder_at
+                .is_some_and(|next_reminder_at| next_reminder_at <= now)
+    }
+
+    /// How many cadences have passed since the contact was last due to be
+    /// seen: 1.0 once their reminder comes, 2.0 a cadence after that, and
+    /// so on. Snoozing pushes the reminder, and so the ratio, back. A
+    /// reminder without a cadence, such as a snooze, counts in months. `None`
+    /// if they aren't due.
+    #[must_use]
+    pub fn overdue_ratio(&self, now: DateTime<Utc>) -> Option<f64> {
+        let days_overdue = self.days_overdue(now)?;
+        let cadence = self.cadence().unwrap_or(Cadence::Monthly);
+
+        Some(1.0 + whole_days(days_overdue) / whole_days(cadence.days()))
+    }
+
+    /// Whole days since the contact's reminder came. `None` if they aren't
+    /// due.
+    #[must_use]
+    pub fn days_overdue(&self, now: DateTime<Utc>) -> Option<i64> {
+        let next_reminder_at = self.next_reminder_at.filter(|_| self.is_due(now))?;
+
+        Some((now - next_reminder_at).num_days())
+    }
+}
+
+/// Day counts as a float without `as`. Saturating is harmless, as no one is
+/// overdue by more than `i32::MAX` days.
+fn whole_days(days: i64) -> f64 {
+    f64::from(i32::try_from(days).unwrap_or(i32::MAX))
 }
 
 #[cfg(test)]
 mod tests {
     use chrono::{Duration, Utc};
 
-    use super::Metadata;
+    use super::{Cadence, Metadata};
 
     #[test]
     fn test_has_default() {
@@ -54,6 +123,7 @@ mod tests {
             next_reminder_at: None,
             frequency: None,
             last_reminder_at: None,
+            group_frequencies: None,
         };
 
         assert_eq!(default_metadata.contact_id, expected_metadata.contact_id);
@@ -88,4 +158,91 @@ mod tests {
             expected_metadata.last_reminder_at
         );
     }
+
+    #[test]
+    fn test_days_since_seen() {
+        let now = Utc::now();
+        let mut metadata = Metadata::new(1);
+        metadata.created_at = now - Duration::days(30);
+
+        assert_eq!(metadata.days_since_seen(now), 30);
+
+        metadata.last_seen_at = Some(now - Duration::days(2));
+
+        assert_eq!(metadata.days_since_seen(now), 2);
+    }
+
+    #[test]
+    fn test_cadence_from_frequency() {
+        let mut metadata = Metadata::new(1);
+
+        assert_eq!(metadata.cadence(), None);
+
+        metadata.frequency = Some("quarterly".to_string());
+
+        assert_eq!(metadata.cadence(), Some(Cadence::Quarterly));
+    }
+
+    #[test]
+    fn test_is_due() {
+        let now = Utc::now();
+        let mut metadata = Metadata::new(1);
+
+        assert!(!metadata.is_due(now));
+
+        metadata.next_reminder_at = Some(now);
+
+        assert!(metadata.is_due(now));
+
+        metadata.is_archived = true;
+
+        assert!(!metadata.is_due(now));
+    }
+
+    #[test]
+    fn test_overdue_ratio() {
+        let now = Utc::now();
+        let mut metadata = Metadata::new(1);
+        metadata.next_reminder_at = Some(now - Duration::days(Cadence::Monthly.days()));
+
+        assert_eq!(metadata.overdue_ratio(now), Some(2.0));
+
+        metadata.next_reminder_at = Some(now - Duration::days(7));
+        metadata.frequency = Some(Cadence::Weekly.as_str().to_string());
+
+        assert_eq!(metadata.overdue_ratio(now), Some(2.0));
+
+        // Snoozed
+        metadata.next_reminder_at = Some(now + Duration::days(7));
+
+        assert_eq!(metadata.overdue_ratio(now), None);
+    }
+
+    #[test]
+    fn test_days_overdue() {
+        let now = Utc::now();
+        let mut metadata = Metadata::new(1);
+
+        assert_eq!(metadata.days_overdue(now), None);
+
+        metadata.next_reminder_at = Some(now - Duration::days(3));
+
+        assert_eq!(metadata.days_overdue(now), Some(3));
+
+        metadata.next_reminder_at = Some(now + Duration::days(3));
+
+        assert_eq!(metadata.days_overdue(now), None);
+    }
+
+    #[test]
+    fn test_cadence_falls_back_to_groups() {
+        let mut metadata = Metadata::new(1);
+        metadata.group_frequencies = Some("monthly weekly".to_string());
+
+        assert_eq!(metadata.cadence(), Some(Cadence::Weekly));
+
+        metadata.frequency = Some("yearly".to_string());
+
+        assert_eq!(metadata.cadence(), Some(Cadence::Yearly));
+    }
 }
@@ -1,8 +1,39 @@
+mod activity;
 mod agent;
+mod attachment;
+mod cadence;
 mod contact;
+mod freeform;
+mod group;
+mod interaction;
+mod messenger;
 mod metadata;
+mod overdue;
+mod required;
+mod source;
+mod status;
 
+pub use activity::Activity;
+pub use attachment::Attachment;
+pub use attachment::Indexed as IndexedAttachment;
+pub use cadence::Cadence;
 pub use contact::Construct as ContactBuilder;
 pub use contact::Contact;
+pub use contact::Field as ContactField;
+pub use contact::FieldUpdate;
 pub use contact::Indexed as IndexedContact;
-pub use metadata::Metadata;
\ No newline at end of file
+pub use contact::New as NewContact;
+pub use contact::WithMetadata as ContactWithMetadata;
+pub use freeform::Freeform;
+pub use group::Group;
+pub use group::Indexed as IndexedGroup;
+pub use interaction::Indexed as IndexedInteraction;
+pub use interaction::Interaction;
+pub use messenger::DeepLinks;
+pub use messenger::Handle as MessengerHandle;
+pub use metadata::Metadata;
+pub use overdue::Tier as OverdueTier;
+pub use overdue::Tiers as OverdueTiers;
+pub use required::Required as RequiredFields;
+pub use source::Source;
+pub use status::Counts as StatusCounts;
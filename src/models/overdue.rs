@@ -0,0 +1,106 @@
+use std::env;
+
+use crate::utils::AppError;
+
+/// Tiers used when `NBD_OVERDUE_TIERS` is unset: due once a full cadence has
+/// passed, slipping at twice that, lost touch at four times.
+const DEFAULT_TIERS: &str = "due=1 slipping=2 lost-touch=4";
+
+/// A named level of neglect, reached once a contact has gone `multiple`
+/// cadences without being seen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tier {
+    pub name: String,
+    pub multiple: f64,
+}
+
+/// Overdue tiers, least to most overdue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tiers {
+    tiers: Vec<Tier>,
+}
+
+impl Tiers {
+    /// Reads tiers from `NBD_OVERDUE_TIERS`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::new(&env::var("NBD_OVERDUE_TIERS").unwrap_or_default())
+    }
+
+    /// Parses whitespace separated `name=multiple` pairs, e.g.
+    /// `slipping=2 lost-touch=4`, skipping any that don't parse. Falls back to
+    /// the default tiers if none do.
+    #[must_use]
+    pub fn new(spec: &str) -> Self {
+        let mut tiers: Vec<Tier> = spec
+            .split_whitespace()
+            .filter_map(|pair| {
+                let (name, multiple) = pair.split_once('=')?;
+                let multiple = multiple.parse::<f64>().ok()?;
+
+                (multiple > 0.0).then(|| Tier {
+                    name: name.to_lowercase(),
+                    multiple,
+                })
+            })
+            .collect();
+
+        if tiers.is_empty() {
+            return Self::new(DEFAULT_TIERS);
+        }
+
+        tiers.sort_by(|a, b| a.multiple.total_cmp(&b.multiple));
+
+        Self { tiers }
+    }
+
+    /// The position and tier reached after `ratio` cadences, if any.
+    #[must_use]
+    pub fn level(&self, ratio: f64) -> Option<(usize, &Tier)> {
+        self.tiers
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, tier)| ratio >= tier.multiple)
+    }
+
+    /// # Errors
+    ///
+    /// This errors if no tier is called `name`
+    pub fn position(&self, name: &str) -> Result<usize, AppError> {
+        self.tiers
+            .iter()
+            .position(|tier| tier.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| AppError::UnknownLevel(name.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tiers;
+    use crate::utils::AppError;
+
+    #[test]
+    fn test_default_tiers() {
+        let tiers = Tiers::new("");
+
+        assert_eq!(tiers.level(0.5), None);
+        assert_eq!(tiers.level(1.0).unwrap().1.name, "due");
+        assert_eq!(tiers.level(3.9).unwrap().1.name, "slipping");
+        assert_eq!(tiers.level(10.0).unwrap().1.name, "lost-touch");
+        assert_eq!(tiers.position("Lost-Touch").unwrap(), 2);
+    }
+
+    #[test]
+    fn test_custom_tiers() {
+        let tiers = Tiers::new("ghosted=6 late=1.5 nonsense cold=x");
+
+        assert_eq!(tiers.level(1.0), None);
+        assert_eq!(tiers.level(2.0).unwrap(), (0, &tiers.tiers[0]));
+        assert_eq!(tiers.level(6.0).unwrap().1.name, "ghosted");
+        assert!(matches!(
+            tiers.position("slipping"),
+            Err(AppError::UnknownLevel(level)) if level == "slipping"
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use std::env;
+
+use super::contact::Construct;
+use super::{Contact, ContactField};
+use crate::utils::AppError;
+
+/// Fields every contact must have besides a display name, e.g. only an
+/// email for a work address book.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Required {
+    fields: Vec<ContactField>,
+}
+
+impl Required {
+    /// Reads required fields from `NBD_REQUIRED_FIELDS`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::new(&env::var("NBD_REQUIRED_FIELDS").unwrap_or_default())
+    }
+
+    /// Parses whitespace or comma separated field names, e.g. `email phone`,
+    /// skipping any that don't parse.
+    #[must_use]
+    pub fn new(spec: &str) -> Self {
+        let fields = spec
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(|field| field.parse().ok())
+            .collect();
+
+        Self { fields }
+    }
+
+    /// # Errors
+    ///
+    /// This errors with `AppError::BlankField` for the first required field
+    /// the contact doesn't have
+    pub fn check(&self, contact: &Contact) -> Result<(), AppError> {
+        self.fields
+            .iter()
+            .find(|field| contact.field(**field).is_none())
+            .map_or_else(
+                || Ok(()),
+                |field| Err(AppError::BlankField(field.column().to_owned())),
+            )
+    }
+
+    /// # Errors
+    ///
+    /// This errors with `AppError::BlankField` if the edit clears a required
+    /// field
+    pub fn check_edit(&self, edit: &Construct) -> Result<(), AppError> {
+        let cleared = edit
+            .update
+            .fields()
+            .into_iter()
+            .find(|(field, update)| update.is_clear() && self.fields.contains(field));
+
+        cleared.map_or_else(
+            || Ok(()),
+            |(field, _)| Err(AppError::BlankField(field.column().to_owned())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Required;
+    use crate::models::{Contact, ContactBuilder, ContactField};
+    use crate::utils::AppError;
+
+    #[test]
+    fn test_nothing_required_by_default() {
+        let contact = Contact::new("Lewis", "Carroll", "", "").unwrap();
+
+        assert!(Required::new("").check(&contact).is_ok());
+    }
+
+    #[test]
+    fn test_required_fields() {
+        let required = Required::new("email, phone nickname");
+
+        let no_phone = Contact::new("Lewis", "Carroll", "lewis@wonderland.com", "").unwrap();
+
+        assert!(matches!(
+            required.check(&no_phone),
+            Err(AppError::BlankField(field)) if field == "phone_number"
+        ));
+
+        let clear_email =
+            ContactBuilder::new(1, None, None, None, None, None, &[ContactField::Email]).unwrap();
+
+        assert!(matches!(
+            required.check_edit(&clear_email),
+            Err(AppError::BlankField(field)) if field == "email"
+        ));
+        assert!(Required::new("phone").check_edit(&clear_email).is_ok());
+    }
+}
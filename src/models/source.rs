@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+
+/// Where a contact came from, e.g. `manual`, `csv:friends.csv` or
+/// `carddav:dav.example.com`, and its id in that system if it has one.
+#[derive(Debug, PartialEq, Eq, Clone, sqlx::FromRow)]
+pub struct Source {
+    pub contact_id: i64,
+    pub source: String,
+    pub external_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Source {
+    pub const MANUAL: &'static str = "manual";
+
+    #[must_use]
+    pub fn new(contact_id: i64, source: &str, external_id: Option<String>) -> Self {
+        Self {
+            contact_id,
+            source: source.to_owned(),
+            external_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[must_use]
+    pub fn manual(contact_id: i64) -> Self {
+        Self::new(contact_id, Self::MANUAL, None)
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.external_id {
+            Some(external_id) => write!(f, "{} ({external_id})", self.source),
+            None => write!(f, "{}", self.source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Source;
+
+    #[test]
+    fn test_display_source() {
+        assert_eq!(Source::manual(1).to_string(), "manual");
+        assert_eq!(
+            Source::new(1, "google", Some("people/c123".to_string())).to_string(),
+            "google (people/c123)"
+        );
+    }
+}
@@ -0,0 +1,43 @@
+/// Counts for a one line summary of the address book. Archived contacts
+/// only count towards `archived`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, sqlx::FromRow)]
+pub struct Counts {
+    pub contacts: i64,
+    pub due: i64,
+    pub upcoming: i64,
+    pub starred: i64,
+    pub archived: i64,
+}
+
+impl Counts {
+    /// `key=value` pairs in a fixed order, for scripts and shell prompts.
+    /// New counters are only ever added at the end.
+    #[must_use]
+    pub fn porcelain(&self) -> String {
+        format!(
+            "contacts={} due={} upcoming={} starred={} archived={}",
+            self.contacts, self.due, self.upcoming, self.starred, self.archived
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Counts;
+
+    #[test]
+    fn test_porcelain() {
+        let counts = Counts {
+            contacts: 12,
+            due: 3,
+            upcoming: 5,
+            starred: 2,
+            archived: 1,
+        };
+
+        assert_eq!(
+            counts.porcelain(),
+            "contacts=12 due=3 upcoming=5 starred=2 archived=1"
+        );
+    }
+}
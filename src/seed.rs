@@ -0,0 +1,168 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::models::{Cadence, Contact, Interaction, Metadata};
+
+const FIRST_NAMES: [&str; 24] = [
+    "Ada", "Alan", "Beatrix", "Carlos", "Dana", "Elif", "Farah", "Grace", "Hiro", "Ines", "Jonas",
+    "Kemi", "Lena", "Mateo", "Nadia", "Omar", "Priya", "Quinn", "Rosa", "Sven", "Tomas", "Uma",
+    "Wen", "Yusuf",
+];
+
+const LAST_NAMES: [&str; 24] = [
+    "Abbott", "Bauer", "Castillo", "Dubois", "Eriksen", "Fischer", "Garcia", "Haddad", "Ito",
+    "Jensen", "Kowalski", "Larsen", "Moreau", "Nakamura", "Okafor", "Petrov", "Quintero", "Rossi",
+    "Santos", "Tanaka", "Ueda", "Varga", "Weber", "Zhang",
+];
+
+const KINDS: [&str; 5] = ["call", "coffee", "message", "email", "reach-out"];
+
+const NOTES: [&str; 10] = [
+    "Caught up about work",
+    "Talked about their new job",
+    "Shared book recommendations",
+    "Planned a hike",
+    "Asked about the kids",
+    "Congratulated them on the move",
+    "Sent a birthday message",
+    "Swapped travel stories",
+    "Promised to meet next month",
+    "Introduced them to a friend",
+];
+
+const CADENCES: [Cadence; 5] = [
+    Cadence::Weekly,
+    Cadence::Biweekly,
+    Cadence::Monthly,
+    Cadence::Quarterly,
+    Cadence::Yearly,
+];
+
+/// A made up contact with the metadata and history it would have built up.
+/// Interactions have a `contact_id` of 0 until the contact is saved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Fake {
+    pub contact: Contact,
+    pub metadata: Metadata,
+    pub interactions: Vec<Interaction>,
+}
+
+/// `count` fake contacts added over the three years before `now`.
+///
+/// The same seed and `now` always give the same contacts. Emails use the
+/// reserved example.com domain and phone numbers the 555 prefix, so none are
+/// real.
+#[must_use]
+pub fn generate(count: usize, seed: u64, now: DateTime<Utc>) -> Vec<Fake> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (0..count).map(|index| fake(&mut rng, index, now)).collect()
+}
+
+fn fake(rng: &mut StdRng, index: usize, now: DateTime<Utc>) -> Fake {
+    let first_name = FIRST_NAMES.choose(rng).expect("names are not empty");
+    let last_name = LAST_NAMES.choose(rng).expect("names are not empty");
+
+    // The index keeps emails unique however many names repeat
+    let email = format!(
+        "{}.{}{index}@example.com",
+        first_name.to_lowercase(),
+        last_name.to_lowercase()
+    );
+    let phone_number = format!(
+        "{}-555-{:04}",
+        rng.gen_range(200..1000),
+        rng.gen_range(0..10000)
+    );
+
+    let contact = Contact::new(first_name, last_name, &email, &phone_number)
+        .expect("generated contacts are valid");
+
+    let created_at = now - Duration::days(rng.gen_range(1..=3 * 365));
+    let span = (now - created_at).num_seconds();
+
+    let mut occurrences: Vec<DateTime<Utc>> = (0..rng.gen_range(0..=12))
+        .map(|_| created_at + Duration::seconds(rng.gen_range(0..=span)))
+        .collect();
+    occurrences.sort_unstable();
+
+    let interactions: Vec<Interaction> = occurrences
+        .into_iter()
+        .map(|occurred_at| Interaction {
+            contact_id: 0,
+            kind: (*KINDS.choose(rng).expect("kinds are not empty")).to_owned(),
+            note: rng
+                .gen_bool(0.5)
+                .then(|| (*NOTES.choose(rng).expect("notes are not empty")).to_owned()),
+            occurred_at,
+        })
+        .collect();
+
+    let cadence = rng
+        .gen_bool(0.75)
+        .then(|| *CADENCES.choose(rng).expect("cadences are not empty"));
+    let last_seen_at = interactions
+        .last()
+        .map(|interaction| interaction.occurred_at);
+
+    let metadata = Metadata {
+        starred: rng.gen_bool(0.1),
+        is_archived: rng.gen_bool(0.05),
+        created_at,
+        updated_at: last_seen_at.unwrap_or(created_at),
+        last_seen_at,
+        next_reminder_at: cadence
+            .map(|cadence| last_seen_at.unwrap_or(created_at) + cadence.duration()),
+        frequency: cadence.map(|cadence| cadence.as_str().to_owned()),
+        ..Metadata::new(0)
+    };
+
+    Fake {
+        contact,
+        metadata,
+        interactions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::generate;
+
+    #[test]
+    fn test_same_seed_same_contacts() {
+        let now = Utc::now();
+
+        let first = generate(50, 7, now);
+
+        assert_eq!(first, generate(50, 7, now));
+        assert_ne!(first, generate(50, 8, now));
+    }
+
+    #[test]
+    fn test_fake_history_is_consistent() {
+        let now = Utc::now();
+
+        for fake in generate(200, 42, now) {
+            assert!(fake
+                .contact
+                .email
+                .is_some_and(|email| email.ends_with("@example.com")));
+
+            let times: Vec<_> = fake.interactions.iter().map(|i| i.occurred_at).collect();
+
+            assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert!(times
+                .iter()
+                .all(|time| *time >= fake.metadata.created_at && *time <= now));
+            assert_eq!(fake.metadata.last_seen_at, times.last().copied());
+            assert_eq!(
+                fake.metadata.next_reminder_at.is_some(),
+                fake.metadata.cadence().is_some()
+            );
+        }
+    }
+}
@@ -1,21 +1,30 @@
 use std::fmt;
 
+use super::{Locale, Messages};
+
 #[derive(Debug)]
 pub enum AppError {
     DatabaseError(sqlx::Error),
     InvalidEmail(String),
     InvalidPhoneNumber(String),
+    InvalidCadence(String),
+    BlankField(String),
+    UnknownField(String),
+    ConflictingUpdate(String),
+    Locked(String),
+    UnknownMessenger(String),
+    BlankHandle(String),
+    UnknownImporter(String),
+    ImporterFailed(String, String),
+    UnknownLevel(String),
+    UnknownGroup(String),
+    DuplicateGroup(String),
     EmptyUpdate,
 }
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::DatabaseError(error) => write!(f, "Database error: {error}"),
-            Self::InvalidEmail(email) => write!(f, "{email} is invalid."),
-            Self::InvalidPhoneNumber(phone) => write!(f, "{phone} is invalid."),
-            Self::EmptyUpdate => write!(f, "Empty update"),
-        }
+        write!(f, "{}", Messages::new(Locale::English).error(self))
     }
 }
 
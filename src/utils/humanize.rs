@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+
+use super::Messages;
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+const MONTH: i64 = 30 * DAY;
+const YEAR: i64 = 365 * DAY;
+
+/// Describes `at` relative to `now`, e.g. "3 weeks ago" or "in 4 days".
+#[must_use]
+pub fn humanize(at: DateTime<Utc>, now: DateTime<Utc>, messages: &Messages) -> String {
+    let seconds = (at - now).num_seconds();
+    let magnitude = seconds.abs();
+
+    if magnitude < MINUTE {
+        return messages.get("time-now", &[]);
+    }
+
+    let (count, unit) = match magnitude {
+        m if m < HOUR => (m / MINUTE, "minute"),
+        m if m < DAY => (m / HOUR, "hour"),
+        m if m < WEEK => (m / DAY, "day"),
+        m if m < MONTH => (m / WEEK, "week"),
+        m if m < YEAR => (m / MONTH, "month"),
+        m => (m / YEAR, "year"),
+    };
+
+    let unit_id = if count == 1 {
+        format!("unit-{unit}")
+    } else {
+        format!("unit-{unit}s")
+    };
+
+    let duration = messages.get(&unit_id, &[("count", &count)]);
+
+    if seconds < 0 {
+        messages.get("time-past", &[("duration", &duration)])
+    } else {
+        messages.get("time-future", &[("duration", &duration)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::humanize;
+    use crate::utils::{Locale, Messages};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_just_now() {
+        let messages = Messages::new(Locale::English);
+        let now = Utc::now();
+
+        assert_eq!(
+            humanize(now - Duration::seconds(5), now, &messages),
+            "just now"
+        );
+    }
+
+    #[test]
+    fn test_past_dates() {
+        let messages = Messages::new(Locale::English);
+        let now = Utc::now();
+
+        assert_eq!(
+            humanize(now - Duration::minutes(1), now, &messages),
+            "1 minute ago"
+        );
+        assert_eq!(
+            humanize(now - Duration::hours(5), now, &messages),
+            "5 hours ago"
+        );
+        assert_eq!(
+            humanize(now - Duration::weeks(3), now, &messages),
+            "3 weeks ago"
+        );
+        assert_eq!(
+            humanize(now - Duration::days(800), now, &messages),
+            "2 years ago"
+        );
+    }
+
+    #[test]
+    fn test_future_dates() {
+        let messages = Messages::new(Locale::English);
+        let now = Utc::now();
+
+        assert_eq!(
+            humanize(now + Duration::days(4), now, &messages),
+            "in 4 days"
+        );
+        assert_eq!(
+            humanize(now + Duration::days(65), now, &messages),
+            "in 2 months"
+        );
+    }
+
+    #[test]
+    fn test_localized_dates() {
+        let messages = Messages::new(Locale::Spanish);
+        let now = Utc::now();
+
+        assert_eq!(
+            humanize(now - Duration::weeks(3), now, &messages),
+            "hace 3 semanas"
+        );
+        assert_eq!(
+            humanize(now + Duration::days(1), now, &messages),
+            "en 1 día"
+        );
+    }
+}
@@ -0,0 +1,559 @@
+use std::env;
+use std::fmt::Display;
+
+use super::AppError;
+
+type Catalog = &'static [(&'static str, &'static str)];
+
+const ENGLISH: Catalog = &[
+    ("contact-saved", "Successfully saved contact { $id }"),
+    ("contact-updated", "Contact updated"),
+    (
+        "prompt-display-name",
+        "Update display name to \"{ $name }\"? [y/N]: ",
+    ),
+    ("contact-deleted", "Successfully deleted contact { $id }"),
+    ("error-database", "Database error: { $error }"),
+    ("error-invalid-email", "{ $email } is invalid."),
+    ("error-invalid-phone", "{ $phone } is invalid."),
+    (
+        "error-invalid-cadence",
+        "{ $cadence } is not a cadence. Use weekly, biweekly, monthly, quarterly or yearly.",
+    ),
+    ("error-blank-field", "{ $field } cannot be blank."),
+    ("error-unknown-field", "{ $field } is not a contact field."),
+    (
+        "error-conflicting-update",
+        "{ $field } cannot be both set and cleared.",
+    ),
+    ("error-empty-update", "Empty update"),
+    ("error-unknown-group", "There is no group called { $name }"),
+    ("error-duplicate-group", "There is already a group called { $name }"),
+    (
+        "error-unknown-level",
+        "{ $level } is not an overdue level. Levels are set by NBD_OVERDUE_TIERS.",
+    ),
+    (
+        "error-unknown-importer",
+        "No importer named { $name }. Install nbd-import-{ $name } on PATH or in NBD_PLUGIN_DIR.",
+    ),
+    ("error-importer-failed", "Importer { $name } failed: { $error }"),
+    (
+        "error-unknown-messenger",
+        "{ $platform } is not a known messenger. Add a link template for it to NBD_MESSENGER_TEMPLATES.",
+    ),
+    ("error-blank-handle", "A { $platform } handle cannot be blank."),
+    (
+        "error-locked",
+        "{ $holder } is writing to the database, waiting for it to finish...",
+    ),
+    ("label-created", "Created"),
+    ("label-updated", "Updated"),
+    ("label-last-seen", "Last seen"),
+    ("label-next-reminder", "Next reminder"),
+    ("label-last-note", "Last note"),
+    ("label-name", "Name"),
+    ("label-email", "Email"),
+    ("label-phone", "Phone"),
+    ("label-unrecognized", "Unrecognized"),
+    ("label-source", "Source"),
+    ("label-groups", "Groups"),
+    (
+        "activity-header",
+        "ID\tName\tLast interaction\tKind\tDays overdue",
+    ),
+    ("prompt-save-contact", "Save this contact? [Y/n]: "),
+    ("contact-discarded", "Contact not saved"),
+    ("roulette-empty", "No contacts to reach out to"),
+    ("roulette-prompt", "[l]og interaction, [s]nooze, s[k]ip: "),
+    ("prompt-note", "Note (optional): "),
+    ("interaction-logged", "Interaction logged"),
+    ("contact-snoozed", "Snoozed, next reminder { $date }"),
+    ("contact-skipped", "Skipped"),
+    (
+        "review-prompt",
+        "[l]og interaction, [s]nooze, [c]hange cadence, [a]rchive, s[k]ip, [q]uit: ",
+    ),
+    (
+        "prompt-cadence",
+        "Cadence (weekly, biweekly, monthly, quarterly, yearly, none): ",
+    ),
+    ("cadence-updated", "Cadence updated"),
+    ("contact-archived", "Contact archived"),
+    ("review-paused", "Review paused, run review again to resume"),
+    ("review-complete", "Review complete"),
+    ("attachment-saved", "Successfully saved attachment { $id }"),
+    ("attachments-empty", "No attachments"),
+    ("attachment-size", "{ $size } bytes"),
+    (
+        "attachment-not-found",
+        "Contact { $contact } has no attachment { $id }",
+    ),
+    ("doctor-integrity-ok", "Integrity check passed"),
+    (
+        "doctor-integrity-failed",
+        "Integrity check failed: { $detail }",
+    ),
+    ("doctor-schema-ok", "Schema is at version { $version }"),
+    (
+        "doctor-schema-mismatch",
+        "Schema is at version { $version } but this build expects { $expected }",
+    ),
+    (
+        "doctor-schema-unknown",
+        "No migration history found, this build expects version { $expected }",
+    ),
+    ("doctor-orphans", "{ $count } orphaned rows in { $table }"),
+    (
+        "doctor-missing-metadata",
+        "{ $count } contacts have no metadata",
+    ),
+    (
+        "doctor-unreferenced-blobs",
+        "{ $count } stored files belong to no attachment",
+    ),
+    ("doctor-invalid-contact", "Contact { $id }: { $problem }"),
+    (
+        "doctor-fixed",
+        "Removed { $removed } orphaned rows and { $blobs } stored files, and created metadata for { $created } contacts",
+    ),
+    ("doctor-fix-hint", "Run doctor --fix to repair these"),
+    ("doctor-healthy", "No problems found"),
+    ("handle-saved", "Saved { $platform } handle"),
+    ("handle-removed", "Removed { $platform } handle"),
+    ("handle-not-found", "Contact { $id } has no { $platform } handle"),
+    ("handles-empty", "Contact { $id } has no messenger handles"),
+    ("rollback-empty", "No contacts came from { $source }"),
+    (
+        "prompt-rollback",
+        "Delete { $count } contacts from { $source }? [y/N]: ",
+    ),
+    (
+        "rollback-complete",
+        "Deleted { $count } contacts from { $source }",
+    ),
+    ("import-skipped", "Skipped record { $row }: { $error }"),
+    (
+        "import-complete",
+        "Imported { $count } contacts from { $source }",
+    ),
+    ("importers-empty", "No importers found"),
+    (
+        "status-summary",
+        "{ $due } due, { $upcoming } due this week, { $contacts } contacts",
+    ),
+    (
+        "seed-complete",
+        "Added { $count } fake contacts with seed { $seed } as { $source }",
+    ),
+    ("due-empty", "Nobody is overdue"),
+    ("due-row", "{ $name }, last seen { $seen }"),
+    ("group-created", "Successfully created group { $name }"),
+    ("group-member-added", "Added contact { $id } to { $name }"),
+    ("time-now", "just now"),
+    ("time-past", "{ $duration } ago"),
+    ("time-future", "in { $duration }"),
+    ("unit-minute", "{ $count } minute"),
+    ("unit-minutes", "{ $count } minutes"),
+    ("unit-hour", "{ $count } hour"),
+    ("unit-hours", "{ $count } hours"),
+    ("unit-day", "{ $count } day"),
+    ("unit-days", "{ $count } days"),
+    ("unit-week", "{ $count } week"),
+    ("unit-weeks", "{ $count } weeks"),
+    ("unit-month", "{ $count } month"),
+    ("unit-months", "{ $count } months"),
+    ("unit-year", "{ $count } year"),
+    ("unit-years", "{ $count } years"),
+];
+
+const SPANISH: Catalog = &[
+    ("contact-saved", "Contacto guardado correctamente { $id }"),
+    ("contact-updated", "Contacto actualizado"),
+    (
+        "prompt-display-name",
+        "¿Cambiar el nombre visible a \"{ $name }\"? [s/N]: ",
+    ),
+    (
+        "contact-deleted",
+        "Contacto eliminado correctamente { $id }",
+    ),
+    ("error-database", "Error de base de datos: { $error }"),
+    ("error-invalid-email", "{ $email } no es válido."),
+    ("error-invalid-phone", "{ $phone } no es válido."),
+    (
+        "error-invalid-cadence",
+        "{ $cadence } no es una frecuencia. Usa weekly, biweekly, monthly, quarterly o yearly.",
+    ),
+    ("error-blank-field", "{ $field } no puede estar vacío."),
+    ("error-unknown-field", "{ $field } no es un campo de contacto."),
+    (
+        "error-conflicting-update",
+        "{ $field } no se puede asignar y borrar a la vez.",
+    ),
+    ("error-empty-update", "Actualización vacía"),
+    ("error-unknown-group", "No hay ningún grupo llamado { $name }"),
+    ("error-duplicate-group", "Ya hay un grupo llamado { $name }"),
+    (
+        "error-unknown-level",
+        "{ $level } no es un nivel de retraso. Los niveles se definen en NBD_OVERDUE_TIERS.",
+    ),
+    (
+        "error-unknown-importer",
+        "No hay ningún importador llamado { $name }. Instala nbd-import-{ $name } en PATH o en NBD_PLUGIN_DIR.",
+    ),
+    (
+        "error-importer-failed",
+        "El importador { $name } falló: { $error }",
+    ),
+    (
+        "error-unknown-messenger",
+        "{ $platform } no es una aplicación de mensajería conocida. Añade una plantilla de enlace en NBD_MESSENGER_TEMPLATES.",
+    ),
+    (
+        "error-blank-handle",
+        "El usuario de { $platform } no puede estar vacío.",
+    ),
+    (
+        "error-locked",
+        "{ $holder } está escribiendo en la base de datos, esperando a que termine...",
+    ),
+    ("label-created", "Creado"),
+    ("label-updated", "Actualizado"),
+    ("label-last-seen", "Visto por última vez"),
+    ("label-next-reminder", "Próximo recordatorio"),
+    ("label-last-note", "Última nota"),
+    ("label-name", "Nombre"),
+    ("label-email", "Correo electrónico"),
+    ("label-phone", "Teléfono"),
+    ("label-unrecognized", "No reconocido"),
+    ("label-source", "Origen"),
+    ("label-groups", "Grupos"),
+    (
+        "activity-header",
+        "ID\tNombre\tÚltima interacción\tTipo\tDías de retraso",
+    ),
+    ("prompt-save-contact", "¿Guardar este contacto? [S/n]: "),
+    ("contact-discarded", "Contacto no guardado"),
+    (
+        "roulette-empty",
+        "No hay contactos con los que ponerse en contacto",
+    ),
+    (
+        "roulette-prompt",
+        "[l] registrar interacción, [s] posponer, [k] saltar: ",
+    ),
+    ("prompt-note", "Nota (opcional): "),
+    ("interaction-logged", "Interacción registrada"),
+    (
+        "contact-snoozed",
+        "Pospuesto, próximo recordatorio { $date }",
+    ),
+    ("contact-skipped", "Omitido"),
+    (
+        "review-prompt",
+        "[l] registrar interacción, [s] posponer, [c] cambiar frecuencia, [a] archivar, [k] saltar, [q] salir: ",
+    ),
+    (
+        "prompt-cadence",
+        "Frecuencia (weekly, biweekly, monthly, quarterly, yearly, none): ",
+    ),
+    ("cadence-updated", "Frecuencia actualizada"),
+    ("contact-archived", "Contacto archivado"),
+    ("review-paused", "Revisión en pausa, ejecuta review de nuevo para continuar"),
+    ("review-complete", "Revisión completada"),
+    (
+        "attachment-saved",
+        "Archivo adjunto guardado correctamente { $id }",
+    ),
+    ("attachments-empty", "No hay archivos adjuntos"),
+    ("attachment-size", "{ $size } bytes"),
+    (
+        "attachment-not-found",
+        "El contacto { $contact } no tiene el archivo adjunto { $id }",
+    ),
+    ("doctor-integrity-ok", "Comprobación de integridad superada"),
+    (
+        "doctor-integrity-failed",
+        "Falló la comprobación de integridad: { $detail }",
+    ),
+    ("doctor-schema-ok", "El esquema está en la versión { $version }"),
+    (
+        "doctor-schema-mismatch",
+        "El esquema está en la versión { $version } pero esta versión espera { $expected }",
+    ),
+    (
+        "doctor-schema-unknown",
+        "No hay historial de migraciones, esta versión espera { $expected }",
+    ),
+    ("doctor-orphans", "{ $count } filas huérfanas en { $table }"),
+    ("doctor-missing-metadata", "{ $count } contactos sin metadatos"),
+    (
+        "doctor-unreferenced-blobs",
+        "{ $count } archivos guardados no pertenecen a ningún adjunto",
+    ),
+    ("doctor-invalid-contact", "Contacto { $id }: { $problem }"),
+    (
+        "doctor-fixed",
+        "Se eliminaron { $removed } filas huérfanas y { $blobs } archivos guardados, y se crearon metadatos para { $created } contactos",
+    ),
+    ("doctor-fix-hint", "Ejecuta doctor --fix para repararlos"),
+    ("doctor-healthy", "No se encontraron problemas"),
+    ("handle-saved", "Usuario de { $platform } guardado"),
+    ("handle-removed", "Usuario de { $platform } eliminado"),
+    (
+        "handle-not-found",
+        "El contacto { $id } no tiene usuario de { $platform }",
+    ),
+    (
+        "handles-empty",
+        "El contacto { $id } no tiene usuarios de mensajería",
+    ),
+    ("rollback-empty", "Ningún contacto proviene de { $source }"),
+    (
+        "prompt-rollback",
+        "¿Eliminar { $count } contactos de { $source }? [s/N]: ",
+    ),
+    (
+        "rollback-complete",
+        "Se eliminaron { $count } contactos de { $source }",
+    ),
+    ("import-skipped", "Registro { $row } omitido: { $error }"),
+    (
+        "import-complete",
+        "Se importaron { $count } contactos de { $source }",
+    ),
+    ("importers-empty", "No se encontraron importadores"),
+    (
+        "status-summary",
+        "{ $due } pendientes, { $upcoming } pendientes esta semana, { $contacts } contactos",
+    ),
+    (
+        "seed-complete",
+        "Se añadieron { $count } contactos falsos con la semilla { $seed } como { $source }",
+    ),
+    ("due-empty", "No hay nadie con retraso"),
+    ("due-row", "{ $name }, visto por última vez { $seen }"),
+    ("group-created", "Grupo { $name } creado correctamente"),
+    ("group-member-added", "Contacto { $id } añadido a { $name }"),
+    ("time-now", "justo ahora"),
+    ("time-past", "hace { $duration }"),
+    ("time-future", "en { $duration }"),
+    ("unit-minute", "{ $count } minuto"),
+    ("unit-minutes", "{ $count } minutos"),
+    ("unit-hour", "{ $count } hora"),
+    ("unit-hours", "{ $count } horas"),
+    ("unit-day", "{ $count } día"),
+    ("unit-days", "{ $count } días"),
+    ("unit-week", "{ $count } semana"),
+    ("unit-weeks", "{ $count } semanas"),
+    ("unit-month", "{ $count } mes"),
+    ("unit-months", "{ $count } meses"),
+    ("unit-year", "{ $count } año"),
+    ("unit-years", "{ $count } años"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// Picks the locale from `NBD_LANG`, then the POSIX variables in order
+    /// of precedence, `LC_ALL`, `LC_MESSAGES` and `LANG`, then English.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// Like `from_env`, reading variables with `var`. Empty variables are
+    /// treated as unset.
+    #[must_use]
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        ["NBD_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .find_map(|name| var(name).filter(|tag| !tag.is_empty()))
+            .map_or(Self::English, |tag| Self::from_tag(&tag))
+    }
+
+    /// Parses a POSIX or BCP 47 tag such as `es_MX.UTF-8` or `es-MX`.
+    #[must_use]
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match language.as_str() {
+            "es" => Self::Spanish,
+            _ => Self::English,
+        }
+    }
+
+    const fn catalog(self) -> Catalog {
+        match self {
+            Self::English => ENGLISH,
+            Self::Spanish => SPANISH,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Messages {
+    locale: Locale,
+}
+
+impl Messages {
+    #[must_use]
+    pub const fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::new(Locale::from_env())
+    }
+
+    /// Looks up `id` in the active catalog, falling back to English and then
+    /// to the id itself, and fills in `{ $name }` placeholders from `args`.
+    #[must_use]
+    pub fn get(&self, id: &str, args: &[(&str, &dyn Display)]) -> String {
+        let template = lookup(self.locale.catalog(), id)
+            .or_else(|| lookup(ENGLISH, id))
+            .unwrap_or(id);
+
+        let mut message = template.to_owned();
+
+        for (name, value) in args {
+            message = message.replace(&format!("{{ ${name} }}"), &value.to_string());
+        }
+
+        message
+    }
+
+    #[must_use]
+    pub fn error(&self, error: &AppError) -> String {
+        match error {
+            AppError::DatabaseError(error) => self.get("error-database", &[("error", error)]),
+            AppError::InvalidEmail(email) => self.get("error-invalid-email", &[("email", email)]),
+            AppError::InvalidPhoneNumber(phone) => {
+                self.get("error-invalid-phone", &[("phone", phone)])
+            }
+            AppError::InvalidCadence(cadence) => {
+                self.get("error-invalid-cadence", &[("cadence", cadence)])
+            }
+            AppError::BlankField(field) => self.get("error-blank-field", &[("field", field)]),
+            AppError::UnknownField(field) => self.get("error-unknown-field", &[("field", field)]),
+            AppError::ConflictingUpdate(field) => {
+                self.get("error-conflicting-update", &[("field", field)])
+            }
+            AppError::UnknownMessenger(platform) => {
+                self.get("error-unknown-messenger", &[("platform", platform)])
+            }
+            AppError::BlankHandle(platform) => {
+                self.get("error-blank-handle", &[("platform", platform)])
+            }
+            AppError::UnknownImporter(name) => {
+                self.get("error-unknown-importer", &[("name", name)])
+            }
+            AppError::ImporterFailed(name, error) => {
+                self.get("error-importer-failed", &[("name", name), ("error", error)])
+            }
+            AppError::UnknownLevel(level) => self.get("error-unknown-level", &[("level", level)]),
+            AppError::UnknownGroup(name) => self.get("error-unknown-group", &[("name", name)]),
+            AppError::DuplicateGroup(name) => self.get("error-duplicate-group", &[("name", name)]),
+            AppError::Locked(holder) => self.get("error-locked", &[("holder", holder)]),
+            AppError::EmptyUpdate => self.get("error-empty-update", &[]),
+        }
+    }
+}
+
+fn lookup(catalog: Catalog, id: &str) -> Option<&'static str> {
+    catalog
+        .iter()
+        .find(|(key, _)| *key == id)
+        .map(|(_, template)| *template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("es_MX.UTF-8"), Locale::Spanish);
+        assert_eq!(Locale::from_tag("es-ES"), Locale::Spanish);
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Locale::English);
+        assert_eq!(Locale::from_tag("C"), Locale::English);
+        assert_eq!(Locale::from_tag(""), Locale::English);
+    }
+
+    #[test]
+    fn test_locale_precedence() {
+        fn vars<'a>(set: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+            move |name| {
+                set.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| (*value).to_string())
+            }
+        }
+
+        let posix = [("LANG", "en_US.UTF-8"), ("LC_MESSAGES", "es_ES.UTF-8")];
+        assert_eq!(Locale::from_vars(vars(&posix)), Locale::Spanish);
+
+        let all = [("LC_ALL", "en_US.UTF-8"), ("LC_MESSAGES", "es_ES.UTF-8")];
+        assert_eq!(Locale::from_vars(vars(&all)), Locale::English);
+
+        let empty = [("LC_ALL", ""), ("LANG", "es_MX.UTF-8")];
+        assert_eq!(Locale::from_vars(vars(&empty)), Locale::Spanish);
+
+        let nbd = [("NBD_LANG", "en"), ("LC_ALL", "es_ES.UTF-8")];
+        assert_eq!(Locale::from_vars(vars(&nbd)), Locale::English);
+    }
+
+    #[test]
+    fn test_message_placeholders() {
+        let messages = Messages::new(Locale::English);
+
+        assert_eq!(
+            messages.get("contact-saved", &[("id", &7)]),
+            "Successfully saved contact 7"
+        );
+    }
+
+    #[test]
+    fn test_spanish_message() {
+        let messages = Messages::new(Locale::Spanish);
+
+        assert_eq!(
+            messages.get("contact-deleted", &[("id", &3)]),
+            "Contacto eliminado correctamente 3"
+        );
+    }
+
+    #[test]
+    fn test_unknown_message_falls_back_to_id() {
+        let messages = Messages::new(Locale::Spanish);
+
+        assert_eq!(messages.get("does-not-exist", &[]), "does-not-exist");
+    }
+
+    #[test]
+    fn test_every_message_has_an_english_fallback() {
+        for (id, _) in SPANISH {
+            assert!(lookup(ENGLISH, id).is_some(), "{id} is missing in English");
+        }
+    }
+
+    #[test]
+    fn test_localized_error() {
+        let error = AppError::InvalidEmail("a@b".to_string());
+
+        assert_eq!(
+            Messages::new(Locale::Spanish).error(&error),
+            "a@b no es válido."
+        );
+    }
+}
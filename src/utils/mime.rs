@@ -0,0 +1,57 @@
+use std::path::Path;
+
+const DEFAULT_MIME: &str = "application/octet-stream";
+
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("heic", "image/heic"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("csv", "text/csv"),
+    ("vcf", "text/vcard"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("zip", "application/zip"),
+];
+
+/// Guesses a MIME type from the file extension.
+#[must_use]
+pub fn guess_mime(filename: &str) -> &'static str {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    MIME_TYPES
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map_or(DEFAULT_MIME, |(_, mime)| *mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::guess_mime;
+
+    #[test]
+    fn test_known_extensions() {
+        assert_eq!(guess_mime("resume.pdf"), "application/pdf");
+        assert_eq!(guess_mime("card.JPG"), "image/jpeg");
+        assert_eq!(guess_mime("notes.txt"), "text/plain");
+    }
+
+    #[test]
+    fn test_unknown_extensions() {
+        assert_eq!(guess_mime("archive.xyz"), "application/octet-stream");
+        assert_eq!(guess_mime("Makefile"), "application/octet-stream");
+    }
+}
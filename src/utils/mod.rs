@@ -1,5 +1,11 @@
 mod errors;
+mod humanize;
+mod i18n;
+mod mime;
 mod validation;
 
 pub use errors::AppError;
+pub use humanize::humanize;
+pub use i18n::{Locale, Messages};
+pub use mime::guess_mime;
 pub use validation::{is_not_valid_email, is_not_valid_phone_number};
@@ -1,10 +1,14 @@
 #[cfg(test)]
-
 mod tests {
     use assert_cmd::Command;
 
     fn create_command() -> Command {
-        Command::cargo_bin(get_cli_name()).unwrap()
+        let mut cmd = Command::cargo_bin(get_cli_name()).unwrap();
+
+        // Assertions match English output, whatever the runner's locale
+        cmd.env("NBD_LANG", "en");
+
+        cmd
     }
 
     fn get_cli_name() -> String {
@@ -49,6 +53,16 @@ mod tests {
             .stdout(predicates::str::contains("Successfully saved contact"));
     }
 
+    #[test]
+    fn test_cli_create_without_name() {
+        let mut cmd = create_command();
+        cmd.arg("create");
+
+        cmd.assert()
+            .failure()
+            .stderr(predicates::str::contains("display_name cannot be blank."));
+    }
+
     #[test]
     fn test_cli_delete_contact() {
         let mut cmd = create_command();
@@ -78,6 +92,595 @@ mod tests {
             .stdout(predicates::str::contains("Successfully deleted contact"));
     }
 
+    #[test]
+    fn test_cli_get_contact_dates() {
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("get").arg(&contact_id);
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Created: just now"));
+
+        cmd = create_command();
+        cmd.arg("get").arg(&contact_id).arg("--absolute-dates");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::is_match(r"Created: \d{4}-\d{2}-\d{2}T").unwrap());
+    }
+
+    #[test]
+    fn test_cli_roulette_skip() {
+        let mut cmd = create_command();
+        cmd.arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .assert()
+            .success();
+
+        cmd = create_command();
+        cmd.arg("roulette").write_stdin("k\n");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Skipped"));
+    }
+
+    #[test]
+    fn test_cli_attach_file() {
+        let data_dir = std::env::temp_dir().join("nbd-cli-attachments");
+
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.env("NBD_DATA_DIR", &data_dir)
+            .arg("attach")
+            .arg(&contact_id)
+            .arg("README.md");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Successfully saved attachment"));
+
+        cmd = create_command();
+        cmd.env("NBD_DATA_DIR", &data_dir)
+            .arg("attachments")
+            .arg(&contact_id);
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("README.md\ttext/markdown"));
+    }
+
+    #[test]
+    fn test_cli_create_freeform_contact() {
+        let mut cmd = create_command();
+        cmd.arg("create")
+            .arg("--freeform")
+            .arg("Jane Doe, jane@acme.com, +1 415 555 0100, Acme Corp")
+            .write_stdin("y\n");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Email: jane@acme.com"))
+            .stdout(predicates::str::contains("Unrecognized: Acme Corp"))
+            .stdout(predicates::str::contains("Successfully saved contact"));
+    }
+
+    #[test]
+    fn test_cli_review_completes() {
+        let mut cmd = create_command();
+        cmd.arg("review").write_stdin("k\n".repeat(100));
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Review complete"));
+    }
+
+    #[test]
+    fn test_cli_edit_recomputes_display_name() {
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("edit")
+            .arg(&contact_id)
+            .arg("--last-name")
+            .arg("Renamed")
+            .write_stdin("y\n");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("First Renamed"))
+            .stdout(predicates::str::contains("Contact updated"));
+
+        cmd = create_command();
+        cmd.arg("get").arg(&contact_id);
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("display_name: \"First Renamed\""));
+    }
+
+    #[test]
+    fn test_cli_edit_clear_phone() {
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("edit").arg(&contact_id).arg("--clear").arg("phone");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Contact updated"));
+
+        cmd = create_command();
+        cmd.arg("get").arg(&contact_id);
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("phone_number: None"));
+    }
+
+    #[test]
+    fn test_cli_edit_blank_name() {
+        let mut cmd = create_command();
+        cmd.arg("edit").arg("1").arg("--first-name").arg(" ");
+
+        cmd.assert()
+            .failure()
+            .stderr(predicates::str::contains("first_name cannot be blank."));
+    }
+
+    #[test]
+    fn test_cli_message_link() {
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("handle")
+            .arg(&contact_id)
+            .arg("telegram")
+            .arg("@first_last");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Saved telegram handle"));
+
+        cmd = create_command();
+        cmd.arg("message").arg(&contact_id).arg("--print");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("https://t.me/first_last"));
+
+        cmd = create_command();
+        cmd.arg("handle").arg(&contact_id).arg("icq").arg("123");
+
+        cmd.assert()
+            .failure()
+            .stderr(predicates::str::contains("icq is not a known messenger"));
+    }
+
+    #[test]
+    fn test_cli_contact_source() {
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("get").arg(&contact_id);
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Source: manual"));
+
+        cmd = create_command();
+        cmd.arg("rollback").arg("csv:never-imported.csv");
+
+        cmd.assert().success().stdout(predicates::str::contains(
+            "No contacts came from csv:never-imported.csv",
+        ));
+    }
+
+    #[test]
+    fn test_cli_get_with_template() {
+        let template = std::env::temp_dir().join("nbd-cli-card.j2");
+        std::fs::write(&template, "{{ last_name }}, {{ first_name }} <{{ email }}>").unwrap();
+
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("get")
+            .arg(&contact_id)
+            .arg("--template")
+            .arg(&template);
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Last, First <test@test.com>"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cli_import_with_plugin() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let plugin_dir = std::env::temp_dir().join("nbd-cli-importers");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+
+        let plugin = plugin_dir.join("nbd-import-fixed");
+        std::fs::write(
+            &plugin,
+            r#"#!/bin/sh
+cat > /dev/null
+echo '[{"first_name": "Ada", "last_name": "Lovelace", "email": "ada@example.com",
+  "phone_number": "123-321-1233", "external_id": "1"},
+  {"first_name": "Bad", "email": "not-an-email", "phone_number": "123-321-1233"}]'
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(&plugin, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut cmd = create_command();
+        cmd.env("NBD_PLUGIN_DIR", &plugin_dir)
+            .arg("import")
+            .arg("Cargo.toml")
+            .arg("--with")
+            .arg("fixed");
+
+        cmd.assert()
+            .success()
+            .stderr(predicates::str::contains("Skipped record 2"))
+            .stdout(predicates::str::contains(
+                "Imported 1 contacts from fixed:Cargo.toml",
+            ));
+
+        cmd = create_command();
+        cmd.arg("rollback").arg("fixed:Cargo.toml").arg("--yes");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("contacts from fixed:Cargo.toml"));
+    }
+
+    #[test]
+    fn test_cli_due_levels() {
+        let mut cmd = create_command();
+        cmd.arg("due").arg("--level").arg("lost-touch");
+
+        cmd.assert().success();
+
+        cmd = create_command();
+        cmd.arg("due").arg("--level").arg("nonsense");
+
+        cmd.assert().failure().stderr(predicates::str::contains(
+            "nonsense is not an overdue level",
+        ));
+    }
+
+    #[test]
+    fn test_cli_group_membership() {
+        let group = format!(
+            "group-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        );
+
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("group")
+            .arg("create")
+            .arg(&group)
+            .arg("--cadence")
+            .arg("monthly");
+
+        cmd.assert().success();
+
+        cmd = create_command();
+        cmd.arg("group").arg("create").arg(&group);
+
+        cmd.assert()
+            .failure()
+            .stderr(predicates::str::contains(format!(
+                "There is already a group called {group}"
+            )));
+
+        cmd = create_command();
+        cmd.arg("group").arg("add").arg(&group).arg(&contact_id);
+
+        cmd.assert().success();
+
+        cmd = create_command();
+        cmd.arg("get").arg(&contact_id);
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains(format!("Groups: {group}")))
+            .stdout(predicates::str::contains("Next reminder"));
+
+        cmd = create_command();
+        cmd.arg("group")
+            .arg("add")
+            .arg("no-such-group")
+            .arg(&contact_id);
+
+        cmd.assert().failure().stderr(predicates::str::contains(
+            "There is no group called no-such-group",
+        ));
+    }
+
+    #[test]
+    fn test_cli_seed_fake_contacts() {
+        let mut cmd = create_command();
+        cmd.arg("seed")
+            .arg("--count")
+            .arg("3")
+            .arg("--fake")
+            .arg("--seed")
+            .arg("7");
+
+        cmd.assert().success().stdout(predicates::str::contains(
+            "Added 3 fake contacts with seed 7",
+        ));
+
+        cmd = create_command();
+        cmd.arg("show").arg("--source").arg("seed:7");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("@example.com"));
+
+        cmd = create_command();
+        cmd.arg("rollback").arg("seed:7").arg("--yes");
+
+        cmd.assert().success();
+    }
+
+    #[test]
+    fn test_cli_seed_without_history() {
+        let mut cmd = create_command();
+        cmd.arg("seed")
+            .arg("--count")
+            .arg("2")
+            .arg("--seed")
+            .arg("8");
+
+        cmd.assert().success().stdout(predicates::str::contains(
+            "Added 2 fake contacts with seed 8",
+        ));
+
+        cmd = create_command();
+        cmd.arg("rollback").arg("seed:8").arg("--yes");
+
+        cmd.assert().success();
+    }
+
+    #[test]
+    fn test_cli_show_with_activity() {
+        let mut cmd = create_command();
+
+        let output = cmd
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com")
+            .arg("--phone-number")
+            .arg("123-321-1233")
+            .output()
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let contact_id = stdout.split_whitespace().last().unwrap().to_string();
+
+        cmd = create_command();
+        cmd.arg("show").arg("--with-activity");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Days overdue"))
+            .stdout(predicates::str::contains(format!(
+                "{contact_id}\tFirst Last\t-\t-\t-"
+            )));
+    }
+
+    #[test]
+    fn test_cli_create_without_phone() {
+        let mut cmd = create_command();
+        cmd.arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com");
+
+        cmd.assert()
+            .success()
+            .stdout(predicates::str::contains("Successfully saved contact"));
+
+        let mut cmd = create_command();
+        cmd.env("NBD_REQUIRED_FIELDS", "email phone")
+            .arg("create")
+            .arg("--first-name")
+            .arg("First")
+            .arg("--last-name")
+            .arg("Last")
+            .arg("--email")
+            .arg("test@test.com");
+
+        cmd.assert()
+            .failure()
+            .stderr(predicates::str::contains("phone_number cannot be blank."));
+
+        let mut cmd = create_command();
+        cmd.env("NBD_REQUIRED_FIELDS", "phone").arg("doctor");
+
+        cmd.assert()
+            .failure()
+            .stdout(predicates::str::contains("phone_number cannot be blank."));
+    }
+
+    #[test]
+    fn test_cli_status_porcelain() {
+        let mut cmd = create_command();
+        cmd.arg("status").arg("--porcelain");
+
+        cmd.assert().success().stdout(
+            predicates::str::is_match(
+                r"^contacts=\d+ due=\d+ upcoming=\d+ starred=\d+ archived=\d+\n$",
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_cli_doctor_checks_integrity() {
+        let mut cmd = create_command();
+        cmd.arg("doctor");
+
+        cmd.assert()
+            .stdout(predicates::str::contains("Integrity check passed"))
+            .stdout(predicates::str::contains("Schema is at version"));
+    }
+
     #[test]
     fn test_nbd_invalid_email() {
         let mut cmd = create_command();
@@ -93,7 +696,7 @@ mod tests {
 
         cmd.assert()
             .failure()
-            .stderr(predicates::str::contains("InvalidEmail"));
+            .stderr(predicates::str::contains("test@.com is invalid."));
     }
 
     #[test]
@@ -111,7 +714,7 @@ mod tests {
 
         cmd.assert()
             .failure()
-            .stderr(predicates::str::contains("InvalidPhone"));
+            .stderr(predicates::str::contains("123-321-123 is invalid."));
     }
 
     #[test]